@@ -0,0 +1,78 @@
+//! Solver benchmarks.
+//!
+//! Run with: `cargo bench --bench solver`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use dicee_engine::core::category::CategorySet;
+use dicee_engine::core::config::DiceConfig;
+use dicee_engine::core::solver::TurnSolver;
+
+/// Compares a position that already holds the best attainable score (early exit,
+/// no keep patterns evaluated) with a near-identical one that needs the full search.
+///
+/// Each iteration uses a fresh solver so memoized values aren't reused.
+fn bench_perfect_score_early_exit(c: &mut Criterion) {
+    let available = CategorySet::all();
+    let dicee = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+    let four_sixes = DiceConfig::from_dice(&[5, 6, 6, 6, 6]);
+
+    let mut group = c.benchmark_group("expected_value");
+    group.bench_function("perfect_position", |b| {
+        b.iter(|| TurnSolver::new().expected_value(black_box(&dicee), 1, &available));
+    });
+    group.bench_function("near_perfect_position", |b| {
+        b.iter(|| TurnSolver::new().expected_value(black_box(&four_sixes), 1, &available));
+    });
+    group.finish();
+}
+
+/// Measures the last-reroll keep search, where keeps that can't beat the best
+/// so far are pruned, on a position with many identical faces and one without.
+fn bench_keep_search(c: &mut Criterion) {
+    let available = CategorySet::all();
+    let four_ones = DiceConfig::from_dice(&[1, 1, 1, 1, 2]);
+    let all_distinct = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+
+    let mut group = c.benchmark_group("keep_search");
+    group.bench_function("repeated_faces", |b| {
+        b.iter(|| TurnSolver::new().expected_value(black_box(&four_ones), 1, &available));
+    });
+    group.bench_function("distinct_faces", |b| {
+        b.iter(|| TurnSolver::new().expected_value(black_box(&all_distinct), 1, &available));
+    });
+    group.bench_function("two_rolls", |b| {
+        b.iter(|| TurnSolver::new().expected_value(black_box(&all_distinct), 2, &available));
+    });
+    group.finish();
+}
+
+/// Compares the cost of the two ways to turn a configuration into a cache key:
+/// packing the counts (what the solver cache uses) and looking up its index.
+fn bench_cache_key(c: &mut Criterion) {
+    let configs: Vec<DiceConfig> = DiceConfig::iter_all().collect();
+
+    let mut group = c.benchmark_group("cache_key");
+    group.bench_function("packed", |b| {
+        b.iter(|| {
+            black_box(&configs)
+                .iter()
+                .fold(0u32, |acc, config| acc ^ config.pack())
+        });
+    });
+    group.bench_function("indexed", |b| {
+        b.iter(|| {
+            black_box(&configs)
+                .iter()
+                .fold(0usize, |acc, config| acc ^ config.to_index().as_usize())
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_perfect_score_early_exit,
+    bench_keep_search,
+    bench_cache_key
+);
+criterion_main!(benches);
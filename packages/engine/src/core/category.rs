@@ -5,9 +5,14 @@
 //! bitmask for tracking which categories are available.
 
 use std::fmt;
+use std::sync::LazyLock;
 
 use serde::{Deserialize, Serialize};
 
+use super::config::{ALL_CONFIGS, FRESH_ROLL_DISTRIBUTION};
+use crate::scoring::rules::{self, score};
+use crate::types::Category as TypesCategory;
+
 // =============================================================================
 // CATEGORY ENUM
 // =============================================================================
@@ -42,6 +47,7 @@ pub enum Category {
     /// 40 points for 5 consecutive values
     LargeStraight = 10,
     /// 50 points for 5 of a kind
+    #[serde(alias = "Yahtzee")]
     Dicee = 11,
     /// Sum of all dice (always valid)
     Chance = 12,
@@ -131,6 +137,31 @@ impl Category {
         }
     }
 
+    /// Returns the bonus par for upper section categories: 3 × face.
+    ///
+    /// Scoring par in every upper category totals exactly the 63-point bonus
+    /// threshold. Returns `None` for lower section categories.
+    #[inline]
+    pub const fn bonus_par(self) -> Option<u8> {
+        match self.upper_face() {
+            Some(face) => Some(3 * face),
+            None => None,
+        }
+    }
+
+    /// Returns the position of an upper section category within the upper section (0-5).
+    ///
+    /// Useful for compact upper-section arrays such as `[u8; 6]`.
+    /// Returns `None` for lower section categories.
+    #[inline]
+    pub const fn upper_index(self) -> Option<usize> {
+        if self.is_upper() {
+            Some(self as usize)
+        } else {
+            None
+        }
+    }
+
     /// Returns the fixed score for this category, if applicable.
     ///
     /// - Full House: 25
@@ -149,6 +180,12 @@ impl Category {
         }
     }
 
+    /// Returns the highest score attainable in this category with any dice.
+    #[inline]
+    pub fn max_score(self) -> u8 {
+        rules::max_score(TypesCategory::all()[self.index()]) as u8
+    }
+
     /// Returns the bit mask for this category in a `CategorySet`.
     #[inline]
     pub const fn mask(self) -> u16 {
@@ -160,8 +197,37 @@ impl Category {
     pub fn iter_all() -> impl Iterator<Item = Self> + ExactSizeIterator {
         Self::ALL.iter().copied()
     }
+
+    /// Returns the probability that a single fresh roll of 5 dice satisfies this category.
+    ///
+    /// Lower section categories count a roll when it meets the category requirements
+    /// (e.g. Full House ≈ 0.0386, Dicee = 6/7776). Upper section categories count a
+    /// roll when it scores nonzero, i.e. shows at least one matching face.
+    #[inline]
+    pub fn fresh_roll_validity_probability(self) -> f64 {
+        FRESH_ROLL_VALIDITY[self.index()]
+    }
 }
 
+/// Fresh-roll validity probability for each category, indexed by category.
+static FRESH_ROLL_VALIDITY: LazyLock<[f64; Category::COUNT]> = LazyLock::new(|| {
+    Category::ALL.map(|cat| {
+        ALL_CONFIGS
+            .iter()
+            .zip(FRESH_ROLL_DISTRIBUTION.iter())
+            .filter(|(config, _)| {
+                let result = score(config, cat);
+                if cat.is_upper() {
+                    result.score > 0
+                } else {
+                    result.valid
+                }
+            })
+            .map(|(_, &probability)| probability)
+            .sum()
+    })
+});
+
 impl fmt::Display for Category {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
@@ -183,6 +249,19 @@ impl fmt::Display for Category {
     }
 }
 
+// =============================================================================
+// SECTION
+// =============================================================================
+
+/// A section of the scoresheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Section {
+    /// Ones through Sixes, which count toward the upper bonus.
+    Upper,
+    /// Three of a Kind through Chance.
+    Lower,
+}
+
 // =============================================================================
 // CATEGORY SET
 // =============================================================================
@@ -204,7 +283,12 @@ impl fmt::Display for Category {
 /// assert!(!available.contains(Category::Dicee));
 /// assert_eq!(available.len(), 12);
 /// ```
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+///
+/// Serializes as `{"bits": 4097}`. Deserializes from that form, a bare bitmask
+/// or an array of category names, e.g. `4097` or `["Ones", "Chance"]`. Telling
+/// these apart needs a self-describing format such as JSON; formats like
+/// bincode cannot deserialize a `CategorySet`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize)]
 #[repr(transparent)]
 pub struct CategorySet {
     bits: u16,
@@ -217,6 +301,24 @@ impl CategorySet {
     /// An empty set with no categories.
     pub const EMPTY: Self = Self { bits: 0 };
 
+    /// Categories with a fixed score: Full House, both straights, and Dicee.
+    pub const FIXED_SCORE: Self = Self {
+        bits: Category::FullHouse.mask()
+            | Category::SmallStraight.mask()
+            | Category::LargeStraight.mask()
+            | Category::Dicee.mask(),
+    };
+
+    /// The n-of-a-kind categories: Three of a Kind and Four of a Kind.
+    pub const N_OF_A_KIND: Self = Self {
+        bits: Category::ThreeOfAKind.mask() | Category::FourOfAKind.mask(),
+    };
+
+    /// Categories scored by summing dice: the upper section and Chance.
+    pub const SUM_BASED: Self = Self {
+        bits: Self::upper_only().bits | Category::Chance.mask(),
+    };
+
     /// Creates an empty category set.
     #[inline]
     pub const fn new() -> Self {
@@ -247,6 +349,30 @@ impl CategorySet {
         }
     }
 
+    /// Creates a set containing every category of a scoresheet section.
+    #[inline]
+    pub const fn from_section(section: Section) -> Self {
+        match section {
+            Section::Upper => Self::upper_only(),
+            Section::Lower => Self::lower_only(),
+        }
+    }
+
+    /// Creates a set of the categories from `from` through `to`, inclusive, in index order.
+    ///
+    /// Returns an empty set if `from` comes after `to`.
+    #[inline]
+    pub const fn range(from: Category, to: Category) -> Self {
+        if from.index() > to.index() {
+            return Self::EMPTY;
+        }
+        let up_to = (to.mask() << 1) - 1;
+        let below = from.mask() - 1;
+        Self {
+            bits: up_to & !below,
+        }
+    }
+
     /// Creates a category set from a raw bitmask.
     ///
     /// Only the lower 13 bits are used.
@@ -331,6 +457,26 @@ impl CategorySet {
         }
     }
 
+    /// Returns the categories in this set but not in `other`.
+    #[inline]
+    pub const fn difference(self, other: Self) -> Self {
+        Self {
+            bits: self.bits & !other.bits,
+        }
+    }
+
+    /// Returns true if every category in this set is also in `other`.
+    #[inline]
+    pub const fn is_subset(self, other: Self) -> bool {
+        self.intersection(other).bits == self.bits
+    }
+
+    /// Returns true if this set contains every category in `other`.
+    #[inline]
+    pub const fn is_superset(self, other: Self) -> bool {
+        other.is_subset(self)
+    }
+
     /// Returns the complement (all categories not in this set).
     #[inline]
     pub const fn complement(self) -> Self {
@@ -339,11 +485,64 @@ impl CategorySet {
         }
     }
 
-    /// Iterates over categories in the set.
+    /// Iterates over categories in the set, in ascending index order.
+    ///
+    /// The order is part of the API contract: it matches [`Category::index`],
+    /// so upper categories always come first.
     #[inline]
     pub fn iter(self) -> CategorySetIter {
         CategorySetIter { bits: self.bits }
     }
+
+    /// Iterates over categories in the set paired with their indices.
+    ///
+    /// Convenient for array-backed scoresheets indexed by [`Category::index`].
+    #[inline]
+    pub fn iter_indexed(self) -> impl Iterator<Item = (usize, Category)> {
+        self.iter().map(|cat| (cat.index(), cat))
+    }
+
+    /// Returns the `k`-th category of the set in ascending index order.
+    ///
+    /// Returns `None` if the set has `k` or fewer members.
+    #[inline]
+    pub const fn nth(self, k: usize) -> Option<Category> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut bits = self.bits;
+        let mut skipped = 0;
+        while skipped < k {
+            bits &= bits - 1; // Clear the lowest member
+            skipped += 1;
+        }
+        Category::from_index(bits.trailing_zeros() as usize)
+    }
+
+    /// Iterates over categories not in the set, without building the complement.
+    #[inline]
+    pub fn iter_complement(self) -> CategorySetIter {
+        CategorySetIter {
+            bits: !self.bits & Self::ALL_MASK,
+        }
+    }
+
+    /// Describes the set as the remaining categories of a game, for game logs.
+    ///
+    /// For example `"2/13 categories remaining: Ones, Full House"`.
+    pub fn describe_progress(self) -> String {
+        let names: Vec<String> = self.iter().map(|cat| cat.to_string()).collect();
+        if names.is_empty() {
+            format!("0/{} categories remaining", Category::COUNT)
+        } else {
+            format!(
+                "{}/{} categories remaining: {}",
+                self.len(),
+                Category::COUNT,
+                names.join(", ")
+            )
+        }
+    }
 }
 
 impl fmt::Debug for CategorySet {
@@ -367,6 +566,38 @@ impl fmt::Display for CategorySet {
     }
 }
 
+impl std::ops::BitOr for CategorySet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl std::ops::BitAnd for CategorySet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl std::ops::Not for CategorySet {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl std::ops::Sub for CategorySet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(rhs)
+    }
+}
+
 impl FromIterator<Category> for CategorySet {
     fn from_iter<I: IntoIterator<Item = Category>>(iter: I) -> Self {
         let mut set = Self::EMPTY;
@@ -386,6 +617,70 @@ impl IntoIterator for CategorySet {
     }
 }
 
+impl<'de> Deserialize<'de> for CategorySet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CategorySetVisitor)
+    }
+}
+
+/// Accepts a category bitmask, a sequence of category names, or a `bits` map.
+struct CategorySetVisitor;
+
+impl<'de> serde::de::Visitor<'de> for CategorySetVisitor {
+    type Value = CategorySet;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a category bitmask, an array of category names or a bits map"
+        )
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        if value > CategorySet::ALL_MASK as u64 {
+            return Err(E::invalid_value(
+                serde::de::Unexpected::Unsigned(value),
+                &"a bitmask of at most 13 bits",
+            ));
+        }
+        Ok(CategorySet::from_bits(value as u16))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        match u64::try_from(value) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Err(E::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"a non-negative bitmask",
+            )),
+        }
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut set = CategorySet::EMPTY;
+        while let Some(category) = seq.next_element::<Category>()? {
+            set.insert(category);
+        }
+        Ok(set)
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut bits = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "bits" {
+                if bits.is_some() {
+                    return Err(serde::de::Error::duplicate_field("bits"));
+                }
+                bits = Some(map.next_value::<u64>()?);
+            } else {
+                map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        }
+        let bits = bits.ok_or_else(|| serde::de::Error::missing_field("bits"))?;
+        self.visit_u64(bits)
+    }
+}
+
 // =============================================================================
 // CATEGORY SET ITERATOR
 // =============================================================================
@@ -428,6 +723,81 @@ impl ExactSizeIterator for CategorySetIter {}
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_category_set_flexible_deserialize() {
+        // Ones (bit 0) and Dicee (bit 11)
+        let expected = CategorySet::new()
+            .with(Category::Ones)
+            .with(Category::Dicee);
+        assert_eq!(expected.bits(), 2049);
+
+        let from_bits: CategorySet = serde_json::from_str("2049").unwrap();
+        let from_names: CategorySet = serde_json::from_str(r#"["Ones","Yahtzee"]"#).unwrap();
+        assert_eq!(from_bits, expected);
+        assert_eq!(from_names, expected);
+
+        let canonical: CategorySet = serde_json::from_str(r#"["Ones","Dicee"]"#).unwrap();
+        assert_eq!(canonical, expected);
+
+        // Serializes in the derived struct shape
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#"{"bits":2049}"#
+        );
+
+        // Out-of-range bitmasks and other JSON types are rejected
+        assert!(serde_json::from_str::<CategorySet>("8192").is_err());
+        assert!(serde_json::from_str::<CategorySet>("-1").is_err());
+        assert!(serde_json::from_str::<CategorySet>(r#""Ones""#).is_err());
+        assert!(serde_json::from_str::<CategorySet>(r#"["Bogus"]"#).is_err());
+    }
+
+    #[test]
+    fn test_category_set_legacy_map_format() {
+        // The derived struct shape round-trips
+        let legacy: CategorySet = serde_json::from_str(r#"{"bits":8191}"#).unwrap();
+        assert_eq!(legacy, CategorySet::all());
+
+        let json = serde_json::to_string(&legacy).unwrap();
+        assert_eq!(serde_json::from_str::<CategorySet>(&json).unwrap(), legacy);
+
+        assert!(serde_json::from_str::<CategorySet>(r#"{"bits":8192}"#).is_err());
+        assert!(serde_json::from_str::<CategorySet>("{}").is_err());
+    }
+
+    #[test]
+    fn test_subset_superset() {
+        let upper = CategorySet::upper_only();
+        let lower = CategorySet::lower_only();
+        let all = CategorySet::all();
+
+        assert!(upper.is_subset(all));
+        assert!(lower.is_subset(all));
+        assert!(all.is_superset(upper));
+        assert!(all.is_superset(lower));
+        assert!(!upper.is_subset(lower));
+        assert!(!all.is_subset(upper));
+        assert!(!upper.is_superset(all));
+
+        // Every set is a subset and superset of itself, and contains the empty set
+        assert!(upper.is_subset(upper));
+        assert!(upper.is_superset(upper));
+        assert!(CategorySet::EMPTY.is_subset(lower));
+        assert!(lower.is_superset(CategorySet::EMPTY));
+    }
+
+    #[test]
+    fn test_upper_index() {
+        assert_eq!(Category::Ones.upper_index(), Some(0));
+        assert_eq!(Category::Sixes.upper_index(), Some(5));
+        for (i, &cat) in Category::UPPER.iter().enumerate() {
+            assert_eq!(cat.upper_index(), Some(i));
+        }
+        for cat in Category::LOWER {
+            assert_eq!(cat.upper_index(), None);
+        }
+    }
+
     #[test]
     fn test_category_count() {
         assert_eq!(Category::COUNT, 13);
@@ -459,6 +829,33 @@ mod tests {
         assert_eq!(Category::Ones.fixed_score(), None);
     }
 
+    #[test]
+    fn test_max_scores() {
+        assert_eq!(Category::Ones.max_score(), 5);
+        assert_eq!(Category::Sixes.max_score(), 30);
+        assert_eq!(Category::Chance.max_score(), 30);
+        assert_eq!(Category::Dicee.max_score(), 50);
+    }
+
+    #[test]
+    fn test_fresh_roll_validity_probability() {
+        let p = Category::Dicee.fresh_roll_validity_probability();
+        assert!((p - 6.0 / 7776.0).abs() < 1e-12);
+
+        // 2 straights × 5! orderings = 240
+        let p = Category::LargeStraight.fresh_roll_validity_probability();
+        assert!((p - 240.0 / 7776.0).abs() < 1e-12);
+
+        let p = Category::FullHouse.fresh_roll_validity_probability();
+        assert!((p - 300.0 / 7776.0).abs() < 1e-12);
+
+        // At least one six: 1 - (5/6)^5
+        let p = Category::Sixes.fresh_roll_validity_probability();
+        assert!((p - (1.0 - (5.0f64 / 6.0).powi(5))).abs() < 1e-12);
+
+        assert!((Category::Chance.fresh_roll_validity_probability() - 1.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_category_set_all() {
         let all = CategorySet::all();
@@ -506,4 +903,154 @@ mod tests {
             assert!(lower.contains(cat));
         }
     }
+
+    #[test]
+    fn test_category_set_iter_complement() {
+        let lower: Vec<_> = CategorySet::upper_only().iter_complement().collect();
+        assert_eq!(lower, Category::LOWER);
+
+        assert_eq!(CategorySet::all().iter_complement().count(), 0);
+        assert_eq!(CategorySet::new().iter_complement().count(), 13);
+    }
+
+    #[test]
+    fn test_category_set_presets() {
+        for cat in Category::iter_all() {
+            assert_eq!(
+                CategorySet::FIXED_SCORE.contains(cat),
+                cat.fixed_score().is_some()
+            );
+            assert_eq!(
+                CategorySet::SUM_BASED.contains(cat),
+                cat.is_upper() || cat == Category::Chance
+            );
+        }
+
+        let n_of_a_kind: Vec<_> = CategorySet::N_OF_A_KIND.iter().collect();
+        assert_eq!(n_of_a_kind, [Category::ThreeOfAKind, Category::FourOfAKind]);
+
+        // The presets partition the sheet
+        let union = CategorySet::FIXED_SCORE
+            .union(CategorySet::N_OF_A_KIND)
+            .union(CategorySet::SUM_BASED);
+        assert!(union.is_full());
+        assert_eq!(
+            CategorySet::FIXED_SCORE.len()
+                + CategorySet::N_OF_A_KIND.len()
+                + CategorySet::SUM_BASED.len(),
+            13
+        );
+    }
+
+    #[test]
+    fn test_category_set_nth() {
+        let all = CategorySet::all();
+        for (k, cat) in Category::iter_all().enumerate() {
+            assert_eq!(all.nth(k), Some(cat));
+        }
+        assert_eq!(all.nth(13), None);
+
+        let set = CategorySet::new()
+            .with(Category::Chance)
+            .with(Category::Twos)
+            .with(Category::FullHouse);
+        assert_eq!(set.nth(0), Some(Category::Twos));
+        assert_eq!(set.nth(1), Some(Category::FullHouse));
+        assert_eq!(set.nth(2), Some(Category::Chance));
+        assert_eq!(set.nth(3), None);
+        assert_eq!(CategorySet::EMPTY.nth(0), None);
+    }
+
+    #[test]
+    fn test_category_set_iter_indexed() {
+        let indexed: Vec<_> = CategorySet::all().iter_indexed().collect();
+        let expected: Vec<_> = Category::iter_all().enumerate().collect();
+        assert_eq!(indexed, expected);
+
+        let lower: Vec<usize> = CategorySet::lower_only()
+            .iter_indexed()
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(lower, (6..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_describe_progress() {
+        let full = CategorySet::all().describe_progress();
+        assert!(full.starts_with("13/13 categories remaining: Ones, Twos"));
+        assert!(full.ends_with("Dicee, Chance"));
+
+        let partial = CategorySet::new()
+            .with(Category::FullHouse)
+            .with(Category::Ones);
+        assert_eq!(
+            partial.describe_progress(),
+            "2/13 categories remaining: Ones, Full House"
+        );
+
+        assert_eq!(
+            CategorySet::new().describe_progress(),
+            "0/13 categories remaining"
+        );
+    }
+
+    #[test]
+    fn test_category_set_ranges() {
+        assert_eq!(
+            CategorySet::range(Category::Ones, Category::Sixes),
+            CategorySet::upper_only()
+        );
+        assert_eq!(
+            CategorySet::from_section(Section::Lower),
+            CategorySet::lower_only()
+        );
+        assert_eq!(
+            CategorySet::from_section(Section::Upper),
+            CategorySet::upper_only()
+        );
+        assert_eq!(
+            CategorySet::range(Category::Ones, Category::Chance),
+            CategorySet::all()
+        );
+
+        let middle = CategorySet::range(Category::ThreeOfAKind, Category::Dicee);
+        assert_eq!(middle.len(), 6);
+        assert!(middle.contains(Category::ThreeOfAKind) && middle.contains(Category::Dicee));
+        assert!(!middle.contains(Category::Chance));
+
+        assert_eq!(
+            CategorySet::range(Category::Fours, Category::Fours),
+            CategorySet::new().with(Category::Fours)
+        );
+        assert!(CategorySet::range(Category::Dicee, Category::Ones).is_empty());
+    }
+
+    #[test]
+    fn test_category_set_operators() {
+        let a = CategorySet::range(Category::Fours, Category::FullHouse);
+        let b = CategorySet::N_OF_A_KIND;
+
+        assert_eq!(a | b, a.union(b));
+        assert_eq!(a & b, a.intersection(b));
+        assert_eq!(!a, a.complement());
+        assert_eq!(a - b, a.difference(b));
+
+        assert!((a - b).contains(Category::Fours));
+        assert!(!(a - b).contains(Category::ThreeOfAKind));
+        assert_eq!(!CategorySet::all(), CategorySet::new());
+        assert_eq!(
+            CategorySet::all() - CategorySet::upper_only(),
+            CategorySet::lower_only()
+        );
+    }
+
+    #[test]
+    fn test_bonus_par() {
+        assert_eq!(Category::Fours.bonus_par(), Some(12));
+        assert_eq!(Category::Ones.bonus_par(), Some(3));
+        assert_eq!(Category::Chance.bonus_par(), None);
+
+        let total: u8 = Category::UPPER.iter().filter_map(|c| c.bonus_par()).sum();
+        assert_eq!(total, 63);
+    }
 }
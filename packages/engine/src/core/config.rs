@@ -20,6 +20,7 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 
 use super::error::DiceeError;
+use super::keep::KeepPattern;
 use crate::{Dice, Result};
 
 // =============================================================================
@@ -115,31 +116,68 @@ impl fmt::Display for ConfigIndex {
 /// assert_eq!(config.count(3), 2);
 /// assert_eq!(config.count(5), 0);
 /// ```
+///
+/// # Serialization
+///
+/// Serializes as `{"counts": [c1, c2, c3, c4, c5, c6]}`, one count per face
+/// from 1 to 6. Deserialization rejects counts that do not sum to 5.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "DiceConfigRepr", try_from = "DiceConfigRepr")]
 pub struct DiceConfig {
     /// Count of dice showing each face value.
     /// Index 0 = count of 1s, index 5 = count of 6s.
     counts: [u8; 6],
 }
 
+/// Wire format of a [`DiceConfig`], kept separate from the in-memory layout.
+#[derive(Serialize, Deserialize)]
+struct DiceConfigRepr {
+    counts: [u8; 6],
+}
+
+impl From<DiceConfig> for DiceConfigRepr {
+    fn from(config: DiceConfig) -> Self {
+        Self {
+            counts: config.counts,
+        }
+    }
+}
+
+impl TryFrom<DiceConfigRepr> for DiceConfig {
+    type Error = DiceeError;
+
+    fn try_from(repr: DiceConfigRepr) -> Result<Self> {
+        Self::from_counts(repr.counts)
+    }
+}
+
 impl DiceConfig {
     /// A configuration with all zeros (invalid, but useful as a starting point).
     pub const ZERO: Self = Self { counts: [0; 6] };
 
     /// Creates a configuration from raw counts.
     ///
-    /// Returns an error if the counts don't sum to 5 or contain invalid values.
+    /// Returns [`DiceeError::InvalidDiceCount`] with the total if the counts
+    /// don't sum to 5.
     pub fn from_counts(counts: [u8; 6]) -> Result<Self> {
-        let sum: u8 = counts.iter().sum();
+        let sum: usize = counts.iter().map(|&c| usize::from(c)).sum();
         if sum != 5 {
-            return Err(DiceeError::InvalidDieValue {
-                value: sum,
-                position: 0,
-            });
+            return Err(DiceeError::InvalidDiceCount(sum));
         }
         Ok(Self { counts })
     }
 
+    /// Combines kept dice counts with rolled dice counts into a configuration.
+    ///
+    /// Returns an error if the combined counts don't total 5 dice.
+    pub fn combine(kept_counts: &[u8; 6], rolled_counts: &[u8; 6]) -> Result<Self> {
+        let mut counts = *kept_counts;
+        for (c, &r) in counts.iter_mut().zip(rolled_counts) {
+            *c = c.saturating_add(r);
+        }
+        Self::from_counts(counts)
+    }
+
     /// Creates a configuration from counts without validation.
     ///
     /// # Safety
@@ -163,12 +201,47 @@ impl DiceConfig {
         Self { counts }
     }
 
+    /// Creates a configuration from an ordered dice array at compile time.
+    ///
+    /// Unlike [`from_dice`](Self::from_dice), invalid dice always panic, which
+    /// in a `const` item is a compile error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dicee_engine::core::DiceConfig;
+    ///
+    /// const FULL_HOUSE: DiceConfig = DiceConfig::from_dice_const([3, 3, 3, 5, 5]);
+    /// assert!(FULL_HOUSE.is_full_house());
+    /// ```
+    pub const fn from_dice_const(dice: [u8; 5]) -> Self {
+        let mut counts = [0u8; 6];
+        let mut i = 0;
+        while i < dice.len() {
+            let d = dice[i];
+            assert!(matches!(d, 1..=6), "Die value must be 1-6");
+            counts[(d - 1) as usize] += 1;
+            i += 1;
+        }
+        Self { counts }
+    }
+
     /// Creates a configuration from an ordered dice array with validation.
     pub fn try_from_dice(dice: &Dice) -> Result<Self> {
         crate::validate_dice(dice)?;
         Ok(Self::from_dice(dice))
     }
 
+    /// Creates a configuration from a slice of dice in any order, with validation.
+    ///
+    /// Returns an error unless the slice holds exactly 5 values in [1, 6].
+    pub fn from_slice(dice: &[u8]) -> Result<Self> {
+        let dice: &Dice = dice
+            .try_into()
+            .map_err(|_| DiceeError::InvalidDiceCount(dice.len()))?;
+        Self::try_from_dice(dice)
+    }
+
     /// Returns the count of dice showing the given face value.
     ///
     /// # Panics
@@ -186,6 +259,30 @@ impl DiceConfig {
         &self.counts
     }
 
+    /// Returns an owned copy of the counts array.
+    #[inline]
+    pub const fn to_counts(&self) -> [u8; 6] {
+        self.counts
+    }
+
+    /// Returns the signed change in each face's count going from `self` to `other`.
+    ///
+    /// Entry `i` is face `i + 1`. The positive entries sum to the number of
+    /// dice that changed.
+    pub fn diff(&self, other: &DiceConfig) -> [i8; 6] {
+        std::array::from_fn(|i| other.counts[i].cast_signed() - self.counts[i].cast_signed())
+    }
+
+    /// Iterates over `(face, count)` pairs for faces 1 through 6.
+    pub fn face_counts(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        (1..=6).zip(self.counts.iter().copied())
+    }
+
+    /// Iterates over `(face, count)` pairs for the faces present, in ascending face order.
+    pub fn nonzero_face_counts(&self) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.face_counts().filter(|&(_, count)| count > 0)
+    }
+
     /// Computes the multiplicity: how many ordered dice produce this configuration.
     ///
     /// Formula: 5! / (n₁! × n₂! × ... × n₆!)
@@ -209,12 +306,65 @@ impl DiceConfig {
             + 6 * self.counts[5]
     }
 
+    /// Returns the sum contributed by dice showing the given face (face × count).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `face` is not in [1, 6].
+    #[inline]
+    pub const fn face_sum(&self, face: u8) -> u8 {
+        face * self.count(face)
+    }
+
+    /// Returns the sum of all dice not showing the given face.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `face` is not in [1, 6].
+    #[inline]
+    pub fn sum_excluding(&self, face: u8) -> u8 {
+        self.sum() - self.face_sum(face)
+    }
+
     /// Returns the maximum count of any single face value.
     #[inline]
     pub fn max_count(&self) -> u8 {
         *self.counts.iter().max().unwrap_or(&0)
     }
 
+    /// Returns true if some face shows at least `n` times.
+    #[inline]
+    pub fn has_at_least(&self, n: u8) -> bool {
+        self.max_count() >= n
+    }
+
+    /// Returns true if some face shows exactly `n` times.
+    ///
+    /// `[3, 3, 3, 5, 5]` has exactly three and exactly two, but not exactly four.
+    #[inline]
+    pub fn has_exactly(&self, n: u8) -> bool {
+        self.counts.contains(&n)
+    }
+
+    /// Returns true if the ordered dice array has this configuration.
+    ///
+    /// Equivalent to `DiceConfig::from_dice(dice) == *self` without building
+    /// the intermediate configuration. Out-of-range dice never match.
+    pub fn matches_dice(&self, dice: &Dice) -> bool {
+        let mut counts = self.counts;
+        for &d in dice {
+            if !(1..=6).contains(&d) {
+                return false;
+            }
+            let count = &mut counts[(d - 1) as usize];
+            if *count == 0 {
+                return false;
+            }
+            *count -= 1;
+        }
+        true
+    }
+
     /// Returns the face value (1-6) with the maximum count, or the highest if tied.
     pub fn mode_face(&self) -> u8 {
         let mut best_face = 6u8;
@@ -248,20 +398,77 @@ impl DiceConfig {
         has_three && has_two
     }
 
+    /// Classifies the configuration by its count pattern, ignoring face values.
+    pub fn shape(&self) -> Shape {
+        let pairs: u8 = self.counts.iter().map(|&c| u8::from(c == 2)).sum();
+        match (self.max_count(), pairs) {
+            (5, _) => Shape::Dicee,
+            (4, _) => Shape::FourKind,
+            (3, 1) => Shape::FullHouse,
+            (3, _) => Shape::Trips,
+            (2, 2) => Shape::TwoPair,
+            (2, _) => Shape::OnePair,
+            _ => Shape::HighCard,
+        }
+    }
+
+    /// Classifies how close this configuration is to a large straight.
+    ///
+    /// An *out* is a missing face that completes a large straight when it
+    /// replaces one die. Duplicates only ever contribute one usable face, so
+    /// `[2, 3, 4, 5, 5]` is an open-ended draw needing a 1 or a 6.
+    pub fn straight_draw(&self) -> StraightDraw {
+        const LOW: u8 = 0b011111; // 1-2-3-4-5
+        const HIGH: u8 = 0b111110; // 2-3-4-5-6
+
+        let mut present = 0u8;
+        for (face, &count) in self.counts.iter().enumerate() {
+            if count > 0 {
+                present |= 1 << face;
+            }
+        }
+
+        let has_run = |run: u8| present & run == run;
+        if has_run(LOW) || has_run(HIGH) {
+            return StraightDraw::Large;
+        }
+
+        let mut outs = 0u8;
+        for straight in [LOW, HIGH] {
+            let missing = straight & !present;
+            if missing.is_power_of_two() {
+                outs |= missing;
+            }
+        }
+
+        let is_small = has_run(0b001111) || has_run(0b011110) || has_run(0b111100);
+
+        match outs.count_ones() {
+            0 => StraightDraw::None,
+            1 if is_small => StraightDraw::Small,
+            1 => StraightDraw::Gutshot,
+            _ => StraightDraw::OpenEnded,
+        }
+    }
+
     /// Converts this configuration to its canonical index.
     ///
     /// Uses combinatorial ranking based on stars-and-bars enumeration.
     pub fn to_index(&self) -> ConfigIndex {
-        // We enumerate configurations in lexicographic order of counts.
-        // This could be optimized with a precomputed lookup table.
-        let mut index = 0u8;
-        for config in Self::iter_all() {
-            if config == *self {
-                return ConfigIndex(index);
+        // Configurations are enumerated in lexicographic order of counts, so the
+        // rank is the number of configurations sharing a prefix but having a
+        // smaller count at the first differing face.
+        let mut index = 0u16;
+        let mut remaining = 5usize;
+        for face in 0..5 {
+            let faces_after = 5 - face;
+            for smaller in 0..self.counts[face] as usize {
+                index += DISTRIBUTIONS[remaining - smaller][faces_after];
             }
-            index += 1;
+            remaining -= self.counts[face] as usize;
         }
-        unreachable!("All valid configurations should be enumerable");
+        debug_assert!(index < 252, "Counts must sum to 5");
+        ConfigIndex(index as u8)
     }
 
     /// Creates a configuration from its canonical index.
@@ -269,6 +476,73 @@ impl DiceConfig {
         ALL_CONFIGS[index.as_usize()]
     }
 
+    /// Returns the index of this configuration within an alternative ordering.
+    ///
+    /// Use this with [`from_index_with`](Self::from_index_with) to map indices
+    /// between implementations that enumerate configurations differently.
+    /// Passing [`ALL_CONFIGS`] matches [`to_index`](Self::to_index).
+    ///
+    /// Returns `None` if this configuration does not appear in `order`.
+    pub fn to_index_with(&self, order: &[DiceConfig; 252]) -> Option<ConfigIndex> {
+        order
+            .iter()
+            .position(|config| config == self)
+            .map(|position| ConfigIndex(position as u8))
+    }
+
+    /// Returns the configuration at an index within an alternative ordering.
+    pub fn from_index_with(index: ConfigIndex, order: &[DiceConfig; 252]) -> Self {
+        order[index.as_usize()]
+    }
+
+    /// Samples a configuration from the distribution of a fresh roll of 5 dice.
+    ///
+    /// Each configuration appears in proportion to its multiplicity, so
+    /// `[1, 2, 3, 4, 5]` is 120 times as likely as `[1, 1, 1, 1, 1]`.
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        use rand::RngExt;
+
+        let mut counts = [0u8; 6];
+        for _ in 0..5 {
+            counts[rng.random_range(0..6usize)] += 1;
+        }
+        Self { counts }
+    }
+
+    /// Samples one of the 252 configurations uniformly, for coverage testing.
+    #[cfg(feature = "rand")]
+    pub fn random_uniform<R: rand::Rng + ?Sized>(rng: &mut R) -> Self {
+        use rand::RngExt;
+
+        ALL_CONFIGS[rng.random_range(0..ConfigIndex::COUNT)]
+    }
+
+    /// Packs the counts into an 18-bit integer, 3 bits per face.
+    ///
+    /// The count of face `f` occupies bits `3(f-1)..3f`. Unlike the index,
+    /// this encoding needs no lookup table and is easy to rebuild elsewhere.
+    pub const fn pack(&self) -> u32 {
+        let mut packed = 0u32;
+        let mut i = 0;
+        while i < 6 {
+            packed |= (self.counts[i] as u32) << (3 * i);
+            i += 1;
+        }
+        packed
+    }
+
+    /// Unpacks a configuration produced by [`pack`](Self::pack).
+    ///
+    /// Returns an error if bits above 18 are set or the counts don't sum to 5.
+    pub fn unpack(packed: u32) -> Result<Self> {
+        if packed >> 18 != 0 {
+            return Err(DiceeError::InvalidPackedConfig(packed));
+        }
+        let counts = std::array::from_fn(|i| ((packed >> (3 * i)) & 0b111) as u8);
+        Self::from_counts(counts).map_err(|_| DiceeError::InvalidPackedConfig(packed))
+    }
+
     /// Iterator over all 252 canonical configurations.
     pub fn iter_all() -> impl Iterator<Item = Self> + ExactSizeIterator {
         ALL_CONFIGS.iter().copied()
@@ -276,23 +550,93 @@ impl DiceConfig {
 
     /// Produces a canonical ordered dice representation.
     ///
-    /// The result is sorted in ascending order.
+    /// The result is sorted in ascending order. Looked up in a precomputed
+    /// table by configuration index.
+    #[inline]
     pub fn to_dice(&self) -> Dice {
-        let mut dice = [0u8; 5];
-        let mut pos = 0;
-        for face in 1..=6 {
-            for _ in 0..self.count(face) {
-                dice[pos] = face;
-                pos += 1;
-            }
-        }
-        dice
+        CONFIG_DICE[self.to_index().as_usize()]
+    }
+
+    /// Renders the sorted dice as Unicode die-face characters (⚀⚁⚂⚃⚄⚅).
+    pub fn to_unicode_faces(&self) -> String {
+        self.to_dice()
+            .iter()
+            .map(|&d| DIE_FACE_CHARS[(d - 1) as usize])
+            .collect()
     }
 
     /// Returns the number of distinct face values present.
     pub fn distinct_faces(&self) -> u8 {
         self.counts.iter().filter(|&&c| c > 0).count() as u8
     }
+
+    /// Relabels faces by a permutation, where `perm[i]` is the new face for face `i + 1`.
+    ///
+    /// Returns an error unless `perm` contains each face 1-6 exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dicee_engine::core::DiceConfig;
+    ///
+    /// // Swap 1s and 6s
+    /// let config = DiceConfig::from_dice(&[1, 1, 2, 3, 6]);
+    /// let swapped = config.relabel(&[6, 2, 3, 4, 5, 1]).unwrap();
+    /// assert_eq!(swapped, DiceConfig::from_dice(&[6, 6, 2, 3, 1]));
+    /// ```
+    pub fn relabel(&self, perm: &[u8; 6]) -> Result<DiceConfig> {
+        let mut seen = [false; 6];
+        for &face in perm {
+            if !(1..=6).contains(&face) || seen[face as usize - 1] {
+                return Err(DiceeError::InvalidPermutation(*perm));
+            }
+            seen[face as usize - 1] = true;
+        }
+
+        let mut counts = [0u8; 6];
+        for (&count, &face) in self.counts.iter().zip(perm) {
+            counts[face as usize - 1] = count;
+        }
+        Ok(Self { counts })
+    }
+
+    /// Returns true if rerolling exactly `dice_to_roll` dice can turn this configuration into `target`.
+    ///
+    /// This holds when some `5 - dice_to_roll` of the current dice also appear
+    /// in `target`, so the reroll can supply the rest. Every target is
+    /// reachable by rerolling all 5 dice; more than 5 is never valid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dicee_engine::core::DiceConfig;
+    ///
+    /// let quads = DiceConfig::from_dice(&[3, 3, 3, 3, 1]);
+    /// assert!(quads.could_become(&DiceConfig::from_dice(&[3, 3, 3, 3, 3]), 1));
+    /// assert!(!quads.could_become(&DiceConfig::from_dice(&[1, 1, 1, 1, 1]), 1));
+    /// ```
+    pub fn could_become(&self, target: &DiceConfig, dice_to_roll: u8) -> bool {
+        if dice_to_roll > 5 {
+            return false;
+        }
+        let shared: u8 = self
+            .counts
+            .iter()
+            .zip(&target.counts)
+            .map(|(&a, &b)| a.min(b))
+            .sum();
+        shared >= 5 - dice_to_roll
+    }
+
+    /// Returns the expected dice sum (the Chance score) after rerolling the dice not in `keep`.
+    ///
+    /// Each rerolled die adds 3.5 in expectation, so this is the kept sum plus
+    /// 3.5 per rerolled die. `keep` must be valid for this configuration.
+    pub fn expected_chance_after_keep(&self, keep: &KeepPattern) -> f64 {
+        debug_assert!(keep.is_valid_for(self), "Keep pattern must be valid");
+        let kept_sum: u8 = (1..=6u8).map(|face| face * keep.count(face)).sum();
+        f64::from(kept_sum) + 3.5 * f64::from(keep.dice_to_roll())
+    }
 }
 
 impl fmt::Debug for DiceConfig {
@@ -312,6 +656,52 @@ impl fmt::Display for DiceConfig {
     }
 }
 
+// =============================================================================
+// STRAIGHT DRAW
+// =============================================================================
+
+/// Relationship of a configuration to a large straight, for draw analysis.
+///
+/// Returned by [`DiceConfig::straight_draw`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StraightDraw {
+    /// Already a large straight.
+    Large,
+    /// A small straight with a single out, e.g. `[1, 2, 3, 4, 4]` needing a 5.
+    Small,
+    /// Two outs complete a large straight, e.g. `[2, 3, 4, 5, 5]` needing a 1 or 6.
+    OpenEnded,
+    /// One out fills an interior gap, e.g. `[1, 2, 3, 5, 6]` needing a 4.
+    Gutshot,
+    /// No single replacement completes a large straight.
+    None,
+}
+
+// =============================================================================
+// SHAPE
+// =============================================================================
+
+/// The count pattern of a configuration, independent of which faces appear.
+///
+/// Returned by [`DiceConfig::shape`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Shape {
+    /// Five of a kind, e.g. `[4, 4, 4, 4, 4]`.
+    Dicee,
+    /// Four of a kind, e.g. `[4, 4, 4, 4, 1]`.
+    FourKind,
+    /// Three of a kind and a pair, e.g. `[3, 3, 3, 5, 5]`.
+    FullHouse,
+    /// Three of a kind with two singles, e.g. `[3, 3, 3, 1, 5]`.
+    Trips,
+    /// Two pairs and a single, e.g. `[2, 2, 4, 4, 1]`.
+    TwoPair,
+    /// One pair and three singles, e.g. `[2, 2, 1, 4, 5]`.
+    OnePair,
+    /// Five distinct faces, e.g. `[1, 2, 3, 4, 5]`.
+    HighCard,
+}
+
 // =============================================================================
 // CONSTANTS
 // =============================================================================
@@ -319,6 +709,13 @@ impl fmt::Display for DiceConfig {
 /// Factorial lookup table for n! where n ∈ [0, 5].
 const FACTORIALS: [u32; 6] = [1, 1, 2, 6, 24, 120];
 
+/// Unicode die-face characters for faces 1-6.
+const DIE_FACE_CHARS: [char; 6] = ['⚀', '⚁', '⚂', '⚃', '⚄', '⚅'];
+
+/// `DISTRIBUTIONS[n][m]` is the number of ways to distribute `n` dice over `m` faces,
+/// C(n + m - 1, m - 1).
+const DISTRIBUTIONS: [[u16; 7]; 6] = generate_distributions();
+
 /// All 252 canonical configurations, precomputed.
 ///
 /// Enumerated in lexicographic order by counts array.
@@ -327,6 +724,47 @@ pub static ALL_CONFIGS: [DiceConfig; 252] = generate_all_configs();
 /// All multiplicities, indexed by configuration index.
 pub static CONFIG_MULTIPLICITIES: [u32; 252] = generate_multiplicities();
 
+/// Probability of rolling each configuration with all five dice, indexed by
+/// configuration index: its multiplicity over 6⁵ = 7776.
+pub static FRESH_ROLL_DISTRIBUTION: [f64; 252] = generate_fresh_roll_distribution();
+
+/// Generate the fresh-roll probabilities at compile time.
+const fn generate_fresh_roll_distribution() -> [f64; 252] {
+    let mults = generate_multiplicities();
+    let mut probs = [0.0f64; 252];
+    let mut i = 0;
+    while i < 252 {
+        probs[i] = mults[i] as f64 / 7776.0;
+        i += 1;
+    }
+    probs
+}
+
+/// Sorted dice of every configuration, indexed by configuration index.
+static CONFIG_DICE: [Dice; 252] = generate_config_dice();
+
+/// Expand every configuration to sorted dice at compile time.
+const fn generate_config_dice() -> [Dice; 252] {
+    let mut all = [[0u8; 5]; 252];
+    let mut idx = 0;
+    while idx < 252 {
+        let counts = ALL_CONFIGS[idx].counts;
+        let mut pos = 0;
+        let mut face = 0;
+        while face < 6 {
+            let mut n = 0;
+            while n < counts[face] {
+                all[idx][pos] = face as u8 + 1;
+                pos += 1;
+                n += 1;
+            }
+            face += 1;
+        }
+        idx += 1;
+    }
+    all
+}
+
 /// Generate all configurations at compile time.
 const fn generate_all_configs() -> [DiceConfig; 252] {
     let mut configs = [DiceConfig::ZERO; 252];
@@ -362,6 +800,25 @@ const fn generate_all_configs() -> [DiceConfig; 252] {
     configs
 }
 
+/// Generate the dice distribution counts at compile time.
+const fn generate_distributions() -> [[u16; 7]; 6] {
+    let mut table = [[0u16; 7]; 6];
+    table[0][0] = 1;
+
+    let mut n = 0;
+    while n < 6 {
+        let mut m = 1;
+        while m < 7 {
+            // Either the first face gets no dice, or it gets at least one
+            table[n][m] = table[n][m - 1] + if n > 0 { table[n - 1][m] } else { 0 };
+            m += 1;
+        }
+        n += 1;
+    }
+
+    table
+}
+
 /// Generate multiplicities at compile time.
 const fn generate_multiplicities() -> [u32; 252] {
     let mut mults = [0u32; 252];
@@ -391,6 +848,198 @@ const fn generate_multiplicities() -> [u32; 252] {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::keep::PartialDice;
+    use crate::transition::table::{keep_evs, TRANSITION_TABLE};
+
+    #[test]
+    fn test_pack_roundtrip() {
+        for config in ALL_CONFIGS.iter() {
+            let packed = config.pack();
+            assert!(packed < 1 << 18);
+            assert_eq!(DiceConfig::unpack(packed).unwrap(), *config);
+        }
+        assert_eq!(DiceConfig::from_dice(&[1, 1, 1, 1, 1]).pack(), 5);
+        assert_eq!(DiceConfig::from_dice(&[6, 6, 6, 6, 6]).pack(), 5 << 15);
+    }
+
+    #[test]
+    fn test_unpack_rejects_invalid() {
+        // Counts summing to 4, and to 7 (four 1s plus three 2s)
+        assert_eq!(
+            DiceConfig::unpack(4),
+            Err(DiceeError::InvalidPackedConfig(4))
+        );
+        assert!(DiceConfig::unpack(4 | (3 << 3)).is_err());
+        // Bits beyond the six counts
+        assert!(DiceConfig::unpack(5 | (1 << 18)).is_err());
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let dice: Vec<u8> = vec![5, 3, 5, 3, 3];
+        assert_eq!(
+            DiceConfig::from_slice(&dice).unwrap(),
+            DiceConfig::from_dice(&[3, 3, 3, 5, 5])
+        );
+
+        assert_eq!(
+            DiceConfig::from_slice(&[1, 2, 3, 4]),
+            Err(DiceeError::InvalidDiceCount(4))
+        );
+        assert_eq!(
+            DiceConfig::from_slice(&[1, 2, 0, 4, 5]),
+            Err(DiceeError::InvalidDieValue {
+                value: 0,
+                position: 2
+            })
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_follows_roll_distribution() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(7);
+        let samples = 200_000;
+        let mut straights = 0;
+        let mut dicees = 0;
+        for _ in 0..samples {
+            let config = DiceConfig::random(&mut rng);
+            if config == DiceConfig::from_dice(&[1, 2, 3, 4, 5]) {
+                straights += 1;
+            }
+            if config.is_dicee() {
+                dicees += 1;
+            }
+        }
+
+        // [1,2,3,4,5] has multiplicity 120 of 7776; a Dicee is 6 of 7776
+        let straight_rate = straights as f64 / samples as f64;
+        assert!((straight_rate - 120.0 / 7776.0).abs() < 0.002);
+        let dicee_rate = dicees as f64 / samples as f64;
+        assert!(dicee_rate < 0.002);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_uniform_covers_configs() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(7);
+        let mut seen = [false; 252];
+        for _ in 0..10_000 {
+            seen[DiceConfig::random_uniform(&mut rng).to_index().as_usize()] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_serde_validates_counts() {
+        let config = DiceConfig::from_dice(&[2, 2, 3, 5, 6]);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<DiceConfig>(&json).unwrap(), config);
+
+        assert!(serde_json::from_str::<DiceConfig>(r#"{"counts":[1,1,1,1,1,1]}"#).is_err());
+        assert!(serde_json::from_str::<DiceConfig>(r#"{"counts":[0,0,0,0,0,4]}"#).is_err());
+
+        // Sum of 10 would break multiplicity and sum
+        let err = serde_json::from_str::<DiceConfig>(r#"{"counts":[5,5,0,0,0,0]}"#).unwrap_err();
+        assert!(err.to_string().contains("Invalid dice count 10"));
+        assert_eq!(
+            DiceConfig::from_counts([5, 5, 0, 0, 0, 0]),
+            Err(DiceeError::InvalidDiceCount(10))
+        );
+        assert_eq!(
+            DiceConfig::from_counts([1, 1, 1, 1, 0, 0]),
+            Err(DiceeError::InvalidDiceCount(4))
+        );
+        assert!(serde_json::from_str::<DiceConfig>(r#"{"counts":[6,0,0,0,0,0]}"#).is_err());
+    }
+
+    #[test]
+    fn test_serde_format() {
+        let config = DiceConfig::from_dice(&[1, 3, 3, 4, 6]);
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(json, r#"{"counts":[1,0,2,1,0,1]}"#);
+        assert_eq!(serde_json::from_str::<DiceConfig>(&json).unwrap(), config);
+    }
+
+    #[test]
+    fn test_to_counts() {
+        for config in ALL_CONFIGS.iter() {
+            assert_eq!(config.to_counts(), *config.counts());
+        }
+    }
+
+    #[test]
+    fn test_shape() {
+        let shape = |dice: [u8; 5]| DiceConfig::from_dice(&dice).shape();
+
+        assert_eq!(shape([3, 3, 3, 5, 5]), Shape::FullHouse);
+        assert_eq!(shape([2, 2, 4, 4, 1]), Shape::TwoPair);
+        assert_eq!(shape([1, 2, 3, 4, 5]), Shape::HighCard);
+        assert_eq!(shape([6, 6, 6, 6, 6]), Shape::Dicee);
+        assert_eq!(shape([6, 6, 6, 6, 1]), Shape::FourKind);
+        assert_eq!(shape([6, 6, 6, 2, 1]), Shape::Trips);
+        assert_eq!(shape([6, 6, 3, 2, 1]), Shape::OnePair);
+
+        // Shape agrees with the scoring predicates
+        for config in DiceConfig::iter_all() {
+            assert_eq!(config.shape() == Shape::FullHouse, config.is_full_house());
+            assert_eq!(config.shape() == Shape::Dicee, config.is_dicee());
+        }
+    }
+
+    #[test]
+    fn test_straight_draw() {
+        let draw = |dice: [u8; 5]| DiceConfig::from_dice(&dice).straight_draw();
+
+        assert_eq!(draw([1, 2, 3, 4, 5]), StraightDraw::Large);
+        assert_eq!(draw([2, 3, 4, 5, 6]), StraightDraw::Large);
+        assert_eq!(draw([2, 3, 4, 5, 5]), StraightDraw::OpenEnded);
+        assert_eq!(draw([1, 2, 3, 5, 6]), StraightDraw::Gutshot);
+        assert_eq!(draw([1, 2, 4, 5, 5]), StraightDraw::Gutshot);
+        assert_eq!(draw([1, 2, 3, 4, 4]), StraightDraw::Small);
+        assert_eq!(draw([1, 2, 3, 4, 6]), StraightDraw::Small);
+        assert_eq!(draw([3, 4, 5, 6, 6]), StraightDraw::Small);
+        assert_eq!(draw([1, 1, 2, 2, 3]), StraightDraw::None);
+        assert_eq!(draw([6, 6, 6, 6, 6]), StraightDraw::None);
+    }
+
+    #[test]
+    fn test_to_index_with_default_order() {
+        for config in ALL_CONFIGS.iter() {
+            assert_eq!(config.to_index_with(&ALL_CONFIGS), Some(config.to_index()));
+        }
+    }
+
+    #[test]
+    fn test_to_index_with_reversed_order() {
+        let mut reversed = ALL_CONFIGS;
+        reversed.reverse();
+
+        for config in ALL_CONFIGS.iter() {
+            let index = config.to_index_with(&reversed).unwrap();
+            assert_eq!(index.as_usize(), 251 - config.to_index().as_usize());
+            assert_eq!(DiceConfig::from_index_with(index, &reversed), *config);
+        }
+
+        // A configuration missing from the ordering has no index
+        let mut partial = ALL_CONFIGS;
+        partial[0] = partial[1];
+        assert_eq!(ALL_CONFIGS[0].to_index_with(&partial), None);
+    }
+
+    #[test]
+    fn test_face_sums() {
+        let config = DiceConfig::from_dice(&[3, 3, 3, 5, 5]);
+        assert_eq!(config.face_sum(3), 9);
+        assert_eq!(config.face_sum(5), 10);
+        assert_eq!(config.face_sum(1), 0);
+        assert_eq!(config.sum_excluding(3), 10);
+        assert_eq!(config.sum_excluding(6), config.sum());
+    }
 
     #[test]
     fn test_config_count() {
@@ -412,6 +1061,17 @@ mod tests {
         assert_eq!(canonical, [1, 3, 3, 4, 6]);
     }
 
+    #[test]
+    fn test_combine() {
+        let kept = [0, 0, 2, 0, 0, 0]; // two 3s
+        let rolled = [0, 0, 1, 0, 2, 0]; // one 3, two 5s
+        let config = DiceConfig::combine(&kept, &rolled).unwrap();
+        assert_eq!(config.to_dice(), [3, 3, 3, 5, 5]);
+
+        let too_many = [0, 0, 1, 0, 2, 1];
+        assert!(DiceConfig::combine(&kept, &too_many).is_err());
+    }
+
     #[test]
     fn test_dicee_detection() {
         let dicee = DiceConfig::from_dice(&[3, 3, 3, 3, 3]);
@@ -440,6 +1100,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_index_matches_enumeration_order() {
+        for (i, config) in ALL_CONFIGS.iter().enumerate() {
+            let index = config.to_index();
+            assert_eq!(index.as_usize(), i);
+            assert_eq!(DiceConfig::from_index(index), *config);
+        }
+    }
+
     #[test]
     fn test_sum() {
         let config = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
@@ -463,6 +1132,178 @@ mod tests {
         let pair = DiceConfig::from_dice(&[1, 1, 2, 3, 4]);
         assert_eq!(pair.multiplicity(), 60);
     }
+
+    #[test]
+    fn test_to_unicode_faces() {
+        let config = DiceConfig::from_dice(&[5, 3, 1, 4, 2]);
+        assert_eq!(config.to_unicode_faces(), "⚀⚁⚂⚃⚄");
+
+        let sixes = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+        assert_eq!(sixes.to_unicode_faces(), "⚅⚅⚅⚅⚅");
+    }
+
+    #[test]
+    fn test_from_dice_const() {
+        const FULL_HOUSE: DiceConfig = DiceConfig::from_dice_const([3, 3, 3, 5, 5]);
+        const STRAIGHT: DiceConfig = DiceConfig::from_dice_const([5, 1, 4, 2, 3]);
+
+        assert_eq!(FULL_HOUSE, DiceConfig::from_dice(&[3, 3, 3, 5, 5]));
+        assert_eq!(STRAIGHT, DiceConfig::from_dice(&[5, 1, 4, 2, 3]));
+        for config in DiceConfig::iter_all() {
+            assert_eq!(DiceConfig::from_dice_const(config.to_dice()), config);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Die value must be 1-6")]
+    fn test_from_dice_const_rejects_invalid() {
+        let _ = DiceConfig::from_dice_const([0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_relabel() {
+        let config = DiceConfig::from_dice(&[1, 1, 2, 3, 4]);
+
+        // Reverse faces: 1↔6, 2↔5, 3↔4
+        let reversed = config.relabel(&[6, 5, 4, 3, 2, 1]).unwrap();
+        assert_eq!(reversed, DiceConfig::from_dice(&[6, 6, 5, 4, 3]));
+        assert_eq!(config.relabel(&[1, 2, 3, 4, 5, 6]).unwrap(), config);
+
+        assert_eq!(
+            config.relabel(&[1, 1, 3, 4, 5, 6]),
+            Err(DiceeError::InvalidPermutation([1, 1, 3, 4, 5, 6]))
+        );
+        assert!(config.relabel(&[0, 2, 3, 4, 5, 6]).is_err());
+        assert!(config.relabel(&[7, 2, 3, 4, 5, 1]).is_err());
+    }
+
+    #[test]
+    fn test_could_become() {
+        let quads = DiceConfig::from_dice(&[3, 3, 3, 3, 1]);
+        let threes = DiceConfig::from_dice(&[3, 3, 3, 3, 3]);
+        let ones = DiceConfig::from_dice(&[1, 1, 1, 1, 1]);
+
+        assert!(quads.could_become(&threes, 1));
+        assert!(quads.could_become(&quads, 0));
+        assert!(!quads.could_become(&threes, 0));
+
+        // Only one 1 can be kept, so four dice must be rerolled
+        assert!(!quads.could_become(&ones, 1));
+        assert!(!quads.could_become(&ones, 3));
+        assert!(quads.could_become(&ones, 4));
+        assert!(quads.could_become(&ones, 5));
+        assert!(!quads.could_become(&ones, 6));
+
+        // Agrees with the transition table for every target
+        for dice_to_roll in 0..=5u8 {
+            let reachable: Vec<DiceConfig> = KeepPattern::iter_valid_for(&quads)
+                .filter(|keep| keep.dice_to_roll() == dice_to_roll)
+                .flat_map(|keep| {
+                    let partial = PartialDice::new(quads, keep).unwrap();
+                    TRANSITION_TABLE.get(&partial)
+                })
+                .map(|entry| DiceConfig::from_index(entry.target))
+                .collect();
+            for target in DiceConfig::iter_all() {
+                assert_eq!(
+                    quads.could_become(&target, dice_to_roll),
+                    reachable.contains(&target)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_chance_after_keep() {
+        for config in DiceConfig::iter_all() {
+            let none = config.expected_chance_after_keep(&KeepPattern::KEEP_NONE);
+            let all = config.expected_chance_after_keep(&KeepPattern::keep_all(&config));
+            assert!((none - 17.5).abs() < 1e-9);
+            assert!((all - f64::from(config.sum())).abs() < 1e-9);
+        }
+
+        // Agrees with the transition table
+        let config = DiceConfig::from_dice(&[2, 5, 6, 6, 1]);
+        let keep = KeepPattern::from_counts([0, 0, 0, 0, 1, 2]).unwrap();
+        let (_, table_ev) = keep_evs(&config, |next| f64::from(next.sum()))
+            .into_iter()
+            .find(|(k, _)| *k == keep)
+            .unwrap();
+        assert!((config.expected_chance_after_keep(&keep) - table_ev).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_to_dice_table_matches_expansion() {
+        for config in DiceConfig::iter_all() {
+            let mut expanded = Vec::with_capacity(5);
+            for face in 1..=6 {
+                for _ in 0..config.count(face) {
+                    expanded.push(face);
+                }
+            }
+            assert_eq!(config.to_dice().to_vec(), expanded, "{config:?}");
+        }
+    }
+
+    #[test]
+    fn test_has_at_least_and_exactly() {
+        let full_house = DiceConfig::from_dice(&[3, 3, 3, 5, 5]);
+        assert!(full_house.has_at_least(3));
+        assert!(!full_house.has_at_least(4));
+        assert!(full_house.has_exactly(3));
+        assert!(full_house.has_exactly(2));
+        assert!(!full_house.has_exactly(4));
+
+        let four = DiceConfig::from_dice(&[4, 4, 4, 4, 2]);
+        assert!(four.has_exactly(4));
+        assert!(four.has_at_least(3));
+        assert!(!four.has_exactly(3));
+    }
+
+    #[test]
+    fn test_matches_dice() {
+        let config = DiceConfig::from_dice(&[2, 2, 5, 5, 5]);
+        let cases: [Dice; 5] = [
+            [5, 2, 5, 2, 5],
+            [2, 2, 5, 5, 5],
+            [2, 2, 2, 5, 5],
+            [1, 2, 3, 4, 5],
+            [5, 5, 5, 5, 5],
+        ];
+        for dice in &cases {
+            assert_eq!(
+                config.matches_dice(dice),
+                DiceConfig::from_dice(dice) == config,
+                "{dice:?}"
+            );
+        }
+        assert!(config.matches_dice(&[5, 2, 5, 2, 5]));
+        assert!(!config.matches_dice(&[2, 2, 2, 5, 5]));
+        assert!(!config.matches_dice(&[0, 2, 5, 5, 5]));
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = DiceConfig::from_dice(&[3, 3, 3, 3, 1]);
+        let after = DiceConfig::from_dice(&[3, 3, 3, 3, 3]);
+        assert_eq!(before.diff(&after), [-1, 0, 1, 0, 0, 0]);
+        assert_eq!(after.diff(&before), [1, 0, -1, 0, 0, 0]);
+        assert_eq!(before.diff(&before), [0; 6]);
+
+        let rerolled = DiceConfig::from_dice(&[1, 2, 4, 5, 6]);
+        let changed: i8 = before.diff(&rerolled).iter().filter(|&&d| d > 0).sum();
+        assert_eq!(changed, 4);
+    }
+
+    #[test]
+    fn test_face_counts() {
+        let config = DiceConfig::from_dice(&[1, 3, 3, 4, 6]);
+        let all: Vec<_> = config.face_counts().collect();
+        assert_eq!(all, vec![(1, 1), (2, 0), (3, 2), (4, 1), (5, 0), (6, 1)]);
+
+        let nonzero: Vec<_> = config.nonzero_face_counts().collect();
+        assert_eq!(nonzero, vec![(1, 1), (3, 2), (4, 1), (6, 1)]);
+    }
 }
 
 #[cfg(test)]
@@ -14,6 +14,36 @@ pub enum DiceeError {
         position: usize,
     },
 
+    /// The wrong number of dice was supplied.
+    #[error("Invalid dice count {0}: must be exactly 5")]
+    InvalidDiceCount(usize),
+
+    /// A die in a text representation could not be parsed as a number.
+    #[error("Invalid die {token:?} at position {position}: not a number")]
+    UnparseableDie {
+        /// The text that failed to parse.
+        token: String,
+        /// The position in the dice list (0-based).
+        position: usize,
+    },
+
+    /// A packed configuration did not encode six counts summing to 5.
+    #[error("Invalid packed configuration {0:#x}: counts must fit 18 bits and sum to 5")]
+    InvalidPackedConfig(u32),
+
+    /// Rolled face counts did not match the number of dice rolled.
+    #[error("Invalid roll counts summing to {sum}: expected {dice_rolled} dice, at most 5")]
+    InvalidRollCounts {
+        /// Sum of the supplied face counts.
+        sum: u32,
+        /// Number of dice said to be rolled.
+        dice_rolled: u8,
+    },
+
+    /// A face relabeling was not a permutation of 1-6.
+    #[error("Invalid face permutation {0:?}: must contain each face 1-6 exactly once")]
+    InvalidPermutation([u8; 6]),
+
     /// A configuration index was outside the valid range [0, 252).
     #[error("Invalid configuration index {0}: must be 0-251")]
     InvalidConfigIndex(u8),
@@ -29,6 +59,10 @@ pub enum DiceeError {
         available: u8,
     },
 
+    /// A policy table did not hold one action per turn state.
+    #[error("Invalid policy table with {0} actions: expected 756")]
+    InvalidPolicyLength(usize),
+
     /// Attempted to reroll when no rolls remain.
     #[error("No rolls remaining in current turn")]
     NoRollsRemaining,
@@ -46,4 +80,109 @@ pub enum DiceeError {
     /// A probability value was outside [0, 1].
     #[error("Invalid probability {0}: must be in [0.0, 1.0]")]
     InvalidProbability(f64),
+
+    /// A count ratio did not describe a probability.
+    #[error("Invalid ratio {favorable}/{total}: total must be positive and favorable <= total")]
+    InvalidRatio {
+        /// Number of favorable outcomes.
+        favorable: u32,
+        /// Total number of outcomes.
+        total: u32,
+    },
+}
+
+/// Broad classes of [`DiceeError`], for aggregating errors in logs and metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// The caller supplied malformed dice, indices or keep patterns.
+    Input,
+    /// The request was well-formed but not allowed in the current turn or game state.
+    State,
+    /// An internal invariant was violated; indicates a bug in the engine.
+    Internal,
+}
+
+impl DiceeError {
+    /// Returns the class of this error.
+    pub const fn class(&self) -> ErrorClass {
+        match self {
+            Self::InvalidDieValue { .. }
+            | Self::InvalidDiceCount(_)
+            | Self::UnparseableDie { .. }
+            | Self::InvalidPackedConfig(_)
+            | Self::InvalidRollCounts { .. }
+            | Self::InvalidPermutation(_)
+            | Self::InvalidConfigIndex(_)
+            | Self::InvalidKeepPattern { .. }
+            | Self::InvalidPolicyLength(_) => ErrorClass::Input,
+            Self::NoRollsRemaining
+            | Self::CategoryAlreadyClaimed(_)
+            | Self::NoCategoriesAvailable => ErrorClass::State,
+            Self::InvalidProbability(_) | Self::InvalidRatio { .. } => ErrorClass::Internal,
+        }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classes() {
+        let cases = [
+            (
+                DiceeError::InvalidDieValue {
+                    value: 7,
+                    position: 0,
+                },
+                ErrorClass::Input,
+            ),
+            (DiceeError::InvalidDiceCount(4), ErrorClass::Input),
+            (
+                DiceeError::UnparseableDie {
+                    token: "x".into(),
+                    position: 1,
+                },
+                ErrorClass::Input,
+            ),
+            (DiceeError::InvalidPackedConfig(0), ErrorClass::Input),
+            (
+                DiceeError::InvalidRollCounts {
+                    sum: 6,
+                    dice_rolled: 5,
+                },
+                ErrorClass::Input,
+            ),
+            (DiceeError::InvalidPermutation([1; 6]), ErrorClass::Input),
+            (DiceeError::InvalidConfigIndex(252), ErrorClass::Input),
+            (
+                DiceeError::InvalidKeepPattern {
+                    face: 1,
+                    requested: 2,
+                    available: 1,
+                },
+                ErrorClass::Input,
+            ),
+            (DiceeError::InvalidPolicyLength(0), ErrorClass::Input),
+            (DiceeError::NoRollsRemaining, ErrorClass::State),
+            (DiceeError::CategoryAlreadyClaimed(3), ErrorClass::State),
+            (DiceeError::NoCategoriesAvailable, ErrorClass::State),
+            (DiceeError::InvalidProbability(1.5), ErrorClass::Internal),
+            (
+                DiceeError::InvalidRatio {
+                    favorable: 2,
+                    total: 1,
+                },
+                ErrorClass::Internal,
+            ),
+        ];
+
+        for (error, class) in cases {
+            assert_eq!(error.class(), class, "{error}");
+        }
+    }
 }
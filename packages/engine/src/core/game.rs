@@ -0,0 +1,230 @@
+//! Playing a game: the state carried between turns, and single turns.
+//!
+//! [`GameState`] records what a game has scored so far. With the `rand`
+//! feature, [`Turn::play`] rolls the dice, follows a solver's recommendations
+//! through the rerolls and scores the final dice.
+
+#[cfg(feature = "rand")]
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+
+use crate::core::category::{Category, CategorySet};
+use crate::core::config::DiceConfig;
+use crate::core::error::DiceeError;
+#[cfg(feature = "rand")]
+use crate::core::keep::PartialDice;
+#[cfg(feature = "rand")]
+use crate::core::solver::TurnSolver;
+#[cfg(feature = "rand")]
+use crate::core::turn::{Action, TurnState};
+use crate::scoring::rules::score;
+use crate::Result;
+
+// =============================================================================
+// GAME STATE
+// =============================================================================
+
+/// What a game has scored so far.
+///
+/// Tracks the open categories, the upper section total and whether the
+/// Dicee box holds 50, which is what the bonus and joker rules depend on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GameState {
+    /// Categories not yet scored.
+    pub remaining: CategorySet,
+    /// Sum of the upper section scores so far.
+    pub upper_total: u8,
+    /// Whether the Dicee category was scored for 50 rather than zeroed.
+    #[serde(alias = "yahtzee_scored_nonzero")]
+    pub dicee_scored_nonzero: bool,
+}
+
+impl GameState {
+    /// Creates the state of a new game: every category open, nothing scored.
+    pub const fn new() -> Self {
+        Self {
+            remaining: CategorySet::all(),
+            upper_total: 0,
+            dicee_scored_nonzero: false,
+        }
+    }
+
+    /// Scores `config` in `category` and updates the state, returning the points.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiceeError::CategoryAlreadyClaimed`] if `category` was already scored.
+    pub fn record_score(&mut self, config: &DiceConfig, category: Category) -> Result<u8> {
+        if !self.remaining.contains(category) {
+            return Err(DiceeError::CategoryAlreadyClaimed(category.index() as u8));
+        }
+
+        let points = score(config, category).score;
+        self.remaining.remove(category);
+        if category.is_upper() {
+            self.upper_total += points;
+        }
+        if category == Category::Dicee && points > 0 {
+            self.dicee_scored_nonzero = true;
+        }
+        Ok(points)
+    }
+
+    /// Returns true if rolling `config` earns a bonus Dicee: it is five of a
+    /// kind and the Dicee box already holds 50.
+    pub fn is_bonus_eligible(&self, config: &DiceConfig) -> bool {
+        self.dicee_scored_nonzero && config.is_dicee()
+    }
+
+    /// Returns true once every category has been scored.
+    pub fn is_complete(&self) -> bool {
+        self.remaining.is_empty()
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// =============================================================================
+// TURN
+// =============================================================================
+
+/// A turn sequencer: initial roll, up to two rerolls, then scoring.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Turn;
+
+#[cfg(feature = "rand")]
+impl Turn {
+    /// Plays one turn, letting `policy` choose every keep and the final category.
+    ///
+    /// Returns the category scored and the points it earned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiceeError::NoCategoriesAvailable`] if `available` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dicee_engine::core::game::Turn;
+    /// use dicee_engine::core::{CategorySet, TurnSolver};
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(3);
+    /// let available = CategorySet::all();
+    /// let (category, points) = Turn::play(&mut rng, &TurnSolver::new(), &available).unwrap();
+    /// assert!(available.contains(category));
+    /// # let _ = points;
+    /// ```
+    pub fn play<R: rand::Rng + ?Sized>(
+        rng: &mut R,
+        policy: &TurnSolver,
+        available: &CategorySet,
+    ) -> Result<(Category, u8)> {
+        if available.is_empty() {
+            return Err(DiceeError::NoCategoriesAvailable);
+        }
+
+        let mut state = TurnState::new(DiceConfig::random(rng), TurnState::MAX_ROLLS);
+        loop {
+            match policy.analyze(&state, available).recommendation {
+                Action::Score { category } => {
+                    return Ok((category, score(&state.config, category).score));
+                }
+                Action::Reroll { keep } => {
+                    let partial = PartialDice::new(state.config, keep)?;
+                    let mut rolled = [0u8; 6];
+                    for _ in 0..partial.dice_to_roll() {
+                        rolled[rng.random_range(0..6usize)] += 1;
+                    }
+                    state = TurnState::new(
+                        partial.combine_with_roll(&rolled),
+                        state.rolls_remaining - 1,
+                    );
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dicee_bonus_tracking() {
+        let dicee = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+        let mut state = GameState::new();
+        assert!(!state.is_bonus_eligible(&dicee));
+
+        assert_eq!(state.record_score(&dicee, Category::Dicee), Ok(50));
+        assert!(state.dicee_scored_nonzero);
+        assert!(state.is_bonus_eligible(&DiceConfig::from_dice(&[2, 2, 2, 2, 2])));
+        assert!(!state.is_bonus_eligible(&DiceConfig::from_dice(&[2, 2, 2, 2, 3])));
+
+        assert_eq!(state.record_score(&dicee, Category::Fours), Ok(20));
+        assert_eq!(state.upper_total, 20);
+        assert_eq!(
+            state.record_score(&dicee, Category::Dicee),
+            Err(DiceeError::CategoryAlreadyClaimed(11))
+        );
+    }
+
+    #[test]
+    fn test_zeroed_dicee_is_not_bonus_eligible() {
+        let mut state = GameState::new();
+        let junk = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+        assert_eq!(state.record_score(&junk, Category::Dicee), Ok(0));
+        assert!(!state.dicee_scored_nonzero);
+        assert!(!state.is_bonus_eligible(&DiceConfig::from_dice(&[6, 6, 6, 6, 6])));
+
+        let restored: GameState =
+            serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+        assert_eq!(restored, state);
+    }
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod turn_tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_play_scores_available_category() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(42);
+        let solver = TurnSolver::new();
+        let available = CategorySet::all()
+            .without(Category::Dicee)
+            .without(Category::Chance);
+
+        for _ in 0..20 {
+            let (category, points) = Turn::play(&mut rng, &solver, &available).unwrap();
+            assert!(available.contains(category));
+            assert!(points <= category.max_score());
+        }
+    }
+
+    #[test]
+    fn test_play_single_category() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(9);
+        let available = CategorySet::new().with(Category::Sixes);
+        let (category, points) = Turn::play(&mut rng, &TurnSolver::new(), &available).unwrap();
+        assert_eq!(category, Category::Sixes);
+        assert_eq!(points % 6, 0);
+    }
+
+    #[test]
+    fn test_play_no_categories() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(1);
+        let result = Turn::play(&mut rng, &TurnSolver::new(), &CategorySet::new());
+        assert!(matches!(result, Err(DiceeError::NoCategoriesAvailable)));
+    }
+}
@@ -0,0 +1,365 @@
+//! Full-game solver across turns.
+//!
+//! The single-turn solver maximizes the score of one turn. Across a whole game,
+//! the best category to enter also depends on what it leaves open for later
+//! turns. This module solves that by backward induction over game states:
+//!
+//! W(C, u) = Σ_D P(D) × V₂(D)
+//!
+//! where:
+//! - C is the set of open categories and u the upper section total so far
+//! - P(D) is the probability of a fresh roll showing configuration D
+//! - V_r(D) is the turn value with r rolls remaining, ending with
+//!   V₀(D) = max_{c∈C} [ s(D,c) + W(C \ {c}, u') ]
+//! - W(∅, u) is the upper bonus if u reached the threshold
+//!
+//! The upper total is capped at the bonus threshold, since only reaching it matters.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::core::category::{Category, CategorySet};
+use crate::core::config::{ConfigIndex, DiceConfig};
+use crate::core::keep::{KeepPattern, PartialDice};
+use crate::core::turn::TurnState;
+use crate::scoring::rules::{
+    joker_choices, score_joker, JokerRule, UPPER_BONUS_THRESHOLD, UPPER_BONUS_VALUE,
+};
+use crate::transition::table::TRANSITION_TABLE;
+
+// =============================================================================
+// GAME KEY
+// =============================================================================
+
+/// Key for the turn-value cache: open categories and (normalized) upper total.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct GameKey {
+    remaining: CategorySet,
+    upper: u8,
+}
+
+impl GameKey {
+    fn new(remaining: &CategorySet, upper_so_far: u8) -> Self {
+        let upper = upper_so_far.min(UPPER_BONUS_THRESHOLD);
+        // With no upper categories open, only whether the bonus was reached matters
+        let upper = if remaining.iter().any(Category::is_upper) || upper == UPPER_BONUS_THRESHOLD {
+            upper
+        } else {
+            0
+        };
+        Self {
+            remaining: *remaining,
+            upper,
+        }
+    }
+}
+
+// =============================================================================
+// GAME SOLVER
+// =============================================================================
+
+/// Backward-induction solver for the expected final score of a game.
+///
+/// Values of game states are computed lazily and memoized, so queries near the
+/// end of a game are cheap while a query on an empty scoresheet solves the
+/// whole game.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use dicee_engine::core::{Category, CategorySet, DiceConfig, GameSolver};
+///
+/// let solver = GameSolver::new();
+///
+/// let config = DiceConfig::from_dice(&[1, 1, 2, 2, 3]);
+/// let remaining = CategorySet::new()
+///     .with(Category::Dicee)
+///     .with(Category::Chance);
+///
+/// let (category, ev) = solver.best_scoring_choice(&config, &remaining, 0);
+/// println!("Enter {category} for an expected {ev:.2} more points");
+/// ```
+pub struct GameSolver {
+    /// Joker rule applied to a Dicee rolled after the Dicee box is filled.
+    joker: JokerRule,
+    /// Memoized expected future score at the start of a turn.
+    turn_values: RefCell<HashMap<GameKey, f64>>,
+}
+
+impl GameSolver {
+    /// Creates a new solver with an empty cache and no joker rule.
+    pub fn new() -> Self {
+        Self::with_joker_rule(JokerRule::Disabled)
+    }
+
+    /// Creates a solver that scores extra Dicees under the given joker rule.
+    pub fn with_joker_rule(joker: JokerRule) -> Self {
+        Self {
+            joker,
+            turn_values: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the joker rule this solver plays under.
+    pub fn joker_rule(&self) -> JokerRule {
+        self.joker
+    }
+
+    /// Clears the memoization cache.
+    pub fn clear_cache(&mut self) {
+        self.turn_values.get_mut().clear();
+    }
+
+    /// Returns the number of cached game states.
+    pub fn cache_size(&self) -> usize {
+        self.turn_values.borrow().len()
+    }
+
+    /// Returns the category to enter for a finished turn that maximizes the
+    /// expected final score, and the expected score still to come.
+    ///
+    /// The returned value includes this entry's score, the expected score of all
+    /// later turns under optimal play, and the upper bonus if it is earned.
+    /// Only categories permitted by the joker rule are considered.
+    /// Ties resolve to the first category in set order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `remaining` is empty.
+    pub fn best_scoring_choice(
+        &self,
+        config: &DiceConfig,
+        remaining: &CategorySet,
+        upper_so_far: u8,
+    ) -> (Category, f64) {
+        assert!(!remaining.is_empty(), "No categories left to score");
+
+        let mut best = (Category::Chance, f64::NEG_INFINITY);
+        for cat in joker_choices(config, remaining, self.joker).iter() {
+            let value = self.entry_value(config, remaining, upper_so_far, cat);
+            if value > best.1 {
+                best = (cat, value);
+            }
+        }
+        best
+    }
+
+    /// Returns the expected final score of a fresh game under optimal play.
+    ///
+    /// This is the headline value of the game: the expected score from an
+    /// empty scoresheet, upper bonus included. Under standard rules it is
+    /// about 245.87. The commonly quoted 254.59 also counts 100-point bonuses
+    /// for extra Dicees, which this solver does not model.
+    ///
+    /// This solves every reachable game state, which takes minutes; the result
+    /// is memoized, so later queries on the same solver are immediate.
+    pub fn expected_game_value(&self) -> f64 {
+        self.expected_future_score(&CategorySet::all(), 0)
+    }
+
+    /// Returns the expected score still to come from the start of a turn.
+    ///
+    /// Includes the upper bonus if it is earned by the end of the game.
+    pub fn expected_future_score(&self, remaining: &CategorySet, upper_so_far: u8) -> f64 {
+        if remaining.is_empty() {
+            return upper_bonus(upper_so_far);
+        }
+
+        let key = GameKey::new(remaining, upper_so_far);
+        if let Some(&value) = self.turn_values.borrow().get(&key) {
+            return value;
+        }
+
+        // Value of ending the turn with each configuration
+        let final_values: Vec<f64> = ConfigIndex::iter_all()
+            .map(|index| {
+                let config = DiceConfig::from_index(index);
+                self.best_scoring_choice(&config, remaining, key.upper).1
+            })
+            .collect();
+
+        // Backward induction over the rerolls within the turn
+        let mut values = final_values.clone();
+        for _ in 0..TurnState::MAX_ROLLS {
+            let previous = values;
+            // Configurations share most keeps, so each is valued only once per roll
+            let mut keep_values: HashMap<KeepPattern, f64> = HashMap::with_capacity(462);
+            values = ConfigIndex::iter_all()
+                .map(|index| {
+                    let config = DiceConfig::from_index(index);
+                    let reroll_value = KeepPattern::iter_valid_for(&config)
+                        .map(|keep| {
+                            *keep_values.entry(keep).or_insert_with(|| {
+                                let partial = unsafe { PartialDice::new_unchecked(keep) };
+                                TRANSITION_TABLE.expected_value(&partial, |next_config| {
+                                    previous[next_config.to_index().as_usize()]
+                                })
+                            })
+                        })
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    reroll_value.max(final_values[index.as_usize()])
+                })
+                .collect();
+        }
+
+        // Expectation over the opening roll
+        let value = TRANSITION_TABLE.expected_value(&PartialDice::keep_none(), |config| {
+            values[config.to_index().as_usize()]
+        });
+
+        self.turn_values.borrow_mut().insert(key, value);
+        value
+    }
+
+    /// Value of entering `config` in `category`: its score plus the value of the rest of the game.
+    fn entry_value(
+        &self,
+        config: &DiceConfig,
+        remaining: &CategorySet,
+        upper_so_far: u8,
+        category: Category,
+    ) -> f64 {
+        let points = score_joker(config, category, remaining, self.joker)
+            .map(|result| result.score)
+            .unwrap_or(0);
+        let upper = if category.is_upper() {
+            upper_so_far.saturating_add(points)
+        } else {
+            upper_so_far
+        };
+        points as f64 + self.expected_future_score(&remaining.without(category), upper)
+    }
+}
+
+impl Default for GameSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the upper bonus earned with the given upper total.
+fn upper_bonus(upper_so_far: u8) -> f64 {
+    if upper_so_far >= UPPER_BONUS_THRESHOLD {
+        UPPER_BONUS_VALUE as f64
+    } else {
+        0.0
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring::rules::score;
+
+    #[test]
+    fn test_forced_upper_joker() {
+        let config = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+        let remaining = CategorySet::new()
+            .with(Category::Fours)
+            .with(Category::LargeStraight);
+
+        // Free choice takes the 40-point joker straight
+        let free = GameSolver::with_joker_rule(JokerRule::FreeChoice);
+        let (category, _) = free.best_scoring_choice(&config, &remaining, 0);
+        assert_eq!(category, Category::LargeStraight);
+
+        // Forced upper must use the open Fours box
+        let forced = GameSolver::with_joker_rule(JokerRule::ForcedUpper);
+        let (category, ev) = forced.best_scoring_choice(&config, &remaining, 0);
+        assert_eq!(category, Category::Fours);
+        assert!(ev > 20.0);
+
+        // With Fours filled, the joker falls through to the straight
+        let lower_only = CategorySet::new().with(Category::LargeStraight);
+        let (category, ev) = forced.best_scoring_choice(&config, &lower_only, 0);
+        assert_eq!(category, Category::LargeStraight);
+        assert!((ev - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_single_category_chance() {
+        let solver = GameSolver::new();
+        let remaining = CategorySet::new().with(Category::Chance);
+
+        // Each die is played alone: one roll is worth 3.5, two rolls 4.25 (keep
+        // 4-6), three rolls 14/3 (keep 5-6), so five dice are worth 70/3
+        let ev = solver.expected_future_score(&remaining, 0);
+        assert!((ev - 70.0 / 3.0).abs() < 1e-9, "Chance EV was {ev}");
+    }
+
+    #[test]
+    fn test_empty_game_awards_bonus() {
+        let solver = GameSolver::new();
+        assert_eq!(solver.expected_future_score(&CategorySet::EMPTY, 62), 0.0);
+        assert_eq!(solver.expected_future_score(&CategorySet::EMPTY, 63), 35.0);
+    }
+
+    #[test]
+    fn test_scratch_dicee_to_save_chance() {
+        let solver = GameSolver::new();
+        let config = DiceConfig::from_dice(&[1, 1, 2, 2, 3]);
+        let remaining = CategorySet::new()
+            .with(Category::Dicee)
+            .with(Category::Chance);
+
+        // The immediate best is Chance (9 points vs 0)...
+        assert!(score(&config, Category::Chance).score > score(&config, Category::Dicee).score);
+
+        // ...but a full turn of Chance later is worth far more than 9 now
+        let (category, ev) = solver.best_scoring_choice(&config, &remaining, 0);
+        assert_eq!(category, Category::Dicee);
+        assert!(ev > 20.0);
+    }
+
+    #[test]
+    fn test_upper_bonus_changes_choice() {
+        let solver = GameSolver::new();
+        // Three 6s reach the bonus from 45; Chance scores more immediately
+        let config = DiceConfig::from_dice(&[6, 6, 6, 5, 5]);
+        let remaining = CategorySet::new()
+            .with(Category::Sixes)
+            .with(Category::Chance);
+
+        let (category, _) = solver.best_scoring_choice(&config, &remaining, 45);
+        assert_eq!(category, Category::Sixes);
+    }
+
+    #[test]
+    fn test_sub_game_value_is_deterministic() {
+        let sheet = CategorySet::lower_only();
+        let first = GameSolver::new().expected_future_score(&sheet, 0);
+        let second = GameSolver::new().expected_future_score(&sheet, 0);
+        assert_eq!(first.to_bits(), second.to_bits());
+    }
+
+    #[test]
+    fn test_reduced_game_values() {
+        let solver = GameSolver::new();
+
+        // Chasing sixes keeps each die a 1 - (5/6)^3 chance of showing one
+        let sixes = CategorySet::new().with(Category::Sixes);
+        let ev = solver.expected_future_score(&sixes, 0);
+        assert!((ev - 30.0 * 91.0 / 216.0).abs() < 1e-9, "Sixes EV was {ev}");
+
+        // A Dicee within three rolls has probability 2783176 / 6^10
+        let dicee = CategorySet::new().with(Category::Dicee);
+        let ev = solver.expected_future_score(&dicee, 0);
+        assert!(
+            (ev - 50.0 * 2_783_176.0 / 60_466_176.0).abs() < 1e-9,
+            "Dicee EV was {ev}"
+        );
+    }
+
+    #[test]
+    #[ignore = "solves the whole game; run with `cargo test --release -- --ignored`"]
+    fn test_expected_game_value() {
+        let solver = GameSolver::new();
+        let value = solver.expected_game_value();
+        assert!((245.0..247.0).contains(&value), "Game value was {value}");
+        assert_eq!(solver.expected_game_value().to_bits(), value.to_bits());
+    }
+}
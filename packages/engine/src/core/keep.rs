@@ -18,8 +18,12 @@ use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
+use super::category::Category;
 use super::config::DiceConfig;
 use super::error::DiceeError;
+use crate::scoring::rules::score;
+use crate::transition::table::TRANSITION_TABLE;
+use crate::transition::Probability;
 use crate::Result;
 
 // =============================================================================
@@ -50,11 +54,26 @@ use crate::Result;
 /// assert!(!invalid.is_valid_for(&config));
 /// ```
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "KeepPatternRepr")]
 pub struct KeepPattern {
     /// Count of dice to keep for each face value.
     kept: [u8; 6],
 }
 
+/// Unvalidated serialized form of a [`KeepPattern`].
+#[derive(Deserialize)]
+struct KeepPatternRepr {
+    kept: [u8; 6],
+}
+
+impl TryFrom<KeepPatternRepr> for KeepPattern {
+    type Error = DiceeError;
+
+    fn try_from(repr: KeepPatternRepr) -> Result<Self> {
+        Self::from_counts(repr.kept)
+    }
+}
+
 impl KeepPattern {
     /// Keep nothing (reroll all 5 dice).
     pub const KEEP_NONE: Self = Self { kept: [0; 6] };
@@ -68,11 +87,11 @@ impl KeepPattern {
     ///
     /// Returns an error if total kept exceeds 5.
     pub fn from_counts(kept: [u8; 6]) -> Result<Self> {
-        let total: u8 = kept.iter().sum();
+        let total: u32 = kept.iter().map(|&c| c as u32).sum();
         if total > 5 {
             return Err(DiceeError::InvalidKeepPattern {
                 face: 0,
-                requested: total,
+                requested: total.min(u8::MAX as u32) as u8,
                 available: 5,
             });
         }
@@ -139,6 +158,42 @@ impl KeepPattern {
         Ok(())
     }
 
+    /// Returns the per-face counts of dice that are rerolled (not kept).
+    ///
+    /// This is the complement of the pattern within `config`. Returns an error
+    /// if the pattern is invalid for the configuration.
+    pub fn rerolled_for(&self, config: &DiceConfig) -> Result<[u8; 6]> {
+        self.validate_for(config)?;
+        let mut rerolled = *config.counts();
+        for (r, &k) in rerolled.iter_mut().zip(&self.kept) {
+            *r -= k;
+        }
+        Ok(rerolled)
+    }
+
+    /// Returns true if this pattern keeps at least the dice kept by `other`.
+    pub fn includes(&self, other: &KeepPattern) -> bool {
+        self.kept.iter().zip(&other.kept).all(|(&a, &b)| a >= b)
+    }
+
+    /// Returns the dice this pattern actually keeps from `config`.
+    ///
+    /// Each face's count is capped at the number present, so the
+    /// [`KEEP_ALL`](Self::KEEP_ALL) placeholder or any over-requested face
+    /// normalizes to a pattern valid for `config`.
+    pub fn normalized_for(&self, config: &DiceConfig) -> Self {
+        let mut kept = self.kept;
+        for (keep, &available) in kept.iter_mut().zip(config.counts()) {
+            *keep = (*keep).min(available);
+        }
+        Self { kept }
+    }
+
+    /// Returns true if both patterns keep the same dice from `config`.
+    pub fn same_effect(&self, other: &KeepPattern, config: &DiceConfig) -> bool {
+        self.normalized_for(config) == other.normalized_for(config)
+    }
+
     /// Iterates over all valid keep patterns for a given configuration.
     ///
     /// For a configuration with counts [n₀, n₁, ..., n₅], this generates
@@ -147,6 +202,17 @@ impl KeepPattern {
         KeepPatternIterator::new(config)
     }
 
+    /// Iterates over valid keep patterns for a configuration that include `locked`.
+    ///
+    /// Patterns are yielded in the same order as [`iter_valid_for`](Self::iter_valid_for).
+    /// Locking [`KEEP_NONE`](Self::KEEP_NONE) yields every valid pattern.
+    pub fn iter_supersets_for(
+        config: &DiceConfig,
+        locked: KeepPattern,
+    ) -> impl Iterator<Item = Self> {
+        Self::iter_valid_for(config).filter(move |keep| keep.includes(&locked))
+    }
+
     /// Returns the number of valid keep patterns for a configuration.
     ///
     /// This is ∏(nᵢ + 1) where nᵢ is the count of face i.
@@ -171,10 +237,15 @@ impl fmt::Display for KeepPattern {
             }
         }
         if parts.is_empty() {
-            write!(f, "keep none")
+            write!(f, "keep none")?;
         } else {
-            write!(f, "keep {}", parts.join(", "))
+            write!(f, "keep {}", parts.join(", "))?;
         }
+        // The alternate form `{:#}` also shows how many dice are rerolled
+        if f.alternate() {
+            write!(f, " (roll {})", self.dice_to_roll())?;
+        }
+        Ok(())
     }
 }
 
@@ -288,6 +359,33 @@ impl PartialDice {
         Self { kept: keep }
     }
 
+    /// Creates a partial dice state for a roll that is partly observed.
+    ///
+    /// Dice already rolled and seen are fixed, so they join `kept` and only
+    /// `still_to_roll` dice remain uncertain. Transitions from the result are
+    /// therefore conditioned on the observed dice.
+    ///
+    /// Returns an error if the kept, observed and unrolled dice don't total 5.
+    pub fn with_partial_roll(
+        kept: KeepPattern,
+        already_rolled: [u8; 6],
+        still_to_roll: u8,
+    ) -> Result<Self> {
+        let mut counts = *kept.counts();
+        for (count, &rolled) in counts.iter_mut().zip(&already_rolled) {
+            *count = count.saturating_add(rolled);
+        }
+
+        let total = counts.iter().map(|&c| c as usize).sum::<usize>() + still_to_roll as usize;
+        if total != 5 {
+            return Err(DiceeError::InvalidDiceCount(total));
+        }
+
+        Ok(Self {
+            kept: KeepPattern::from_counts(counts)?,
+        })
+    }
+
     /// Creates a partial dice state representing keeping nothing (full reroll).
     pub const fn keep_none() -> Self {
         Self {
@@ -353,6 +451,24 @@ impl PartialDice {
 
         unsafe { DiceConfig::from_counts_unchecked(counts) }
     }
+
+    /// Returns every reachable configuration with its probability and its score in `category`.
+    ///
+    /// Outcomes are in canonical configuration order.
+    pub fn outcome_scores(&self, category: Category) -> Vec<(DiceConfig, Probability, u8)> {
+        TRANSITION_TABLE
+            .get(self)
+            .map(|entry| {
+                let config = DiceConfig::from_index(entry.target);
+                (config, entry.probability, score(&config, category).score)
+            })
+            .collect()
+    }
+
+    /// Returns the expected score in `category` after rolling the remaining dice.
+    pub fn expected_score(&self, category: Category) -> f64 {
+        TRANSITION_TABLE.expected_value(self, |config| score(config, category).score as f64)
+    }
 }
 
 impl fmt::Debug for PartialDice {
@@ -405,6 +521,17 @@ mod tests {
         assert!(!keep.is_valid_for(&config));
     }
 
+    #[test]
+    fn test_rerolled_for() {
+        let config = DiceConfig::from_dice(&[2, 2, 3, 4, 4]);
+        let keep = KeepPattern::from_counts([0, 2, 0, 2, 0, 0]).unwrap();
+
+        assert_eq!(keep.rerolled_for(&config), Ok([0, 0, 1, 0, 0, 0]));
+
+        let invalid = KeepPattern::from_counts([0, 3, 0, 0, 0, 0]).unwrap();
+        assert!(invalid.rerolled_for(&config).is_err());
+    }
+
     #[test]
     fn test_keep_pattern_iteration() {
         // Config: [1, 2, 3, 3, 5] => counts = [1, 1, 2, 0, 1, 0]
@@ -417,6 +544,24 @@ mod tests {
         assert_eq!(patterns.len(), expected);
     }
 
+    #[test]
+    fn test_iter_supersets_for() {
+        let config = DiceConfig::from_dice(&[3, 3, 3, 3, 6]);
+        let locked = KeepPattern::from_counts([0, 0, 4, 0, 0, 0]).unwrap();
+
+        let patterns: Vec<_> = KeepPattern::iter_supersets_for(&config, locked).collect();
+        assert_eq!(
+            patterns,
+            vec![
+                KeepPattern::from_counts([0, 0, 4, 0, 0, 0]).unwrap(),
+                KeepPattern::from_counts([0, 0, 4, 0, 0, 1]).unwrap(),
+            ]
+        );
+
+        let unlocked = KeepPattern::iter_supersets_for(&config, KeepPattern::KEEP_NONE).count();
+        assert_eq!(unlocked, KeepPattern::count_valid_for(&config));
+    }
+
     #[test]
     fn test_partial_dice_combine() {
         let config = DiceConfig::from_dice(&[2, 2, 3, 4, 4]);
@@ -434,6 +579,54 @@ mod tests {
         assert_eq!(result.sum(), 2 + 2 + 4 + 4 + 6);
     }
 
+    #[test]
+    fn test_outcome_scores() {
+        let config = DiceConfig::from_dice(&[6, 6, 6, 2, 1]);
+        let keep = KeepPattern::from_counts([0, 0, 0, 0, 0, 3]).unwrap();
+        let partial = PartialDice::new(config, keep).unwrap();
+
+        for category in Category::iter_all() {
+            let outcomes = partial.outcome_scores(category);
+            assert_eq!(outcomes.len(), 21); // Two dice: C(7, 2) outcomes
+
+            let total: f64 = outcomes.iter().map(|(_, p, _)| p.get()).sum();
+            let ev: f64 = outcomes
+                .iter()
+                .map(|(_, p, s)| p.get() * f64::from(*s))
+                .sum();
+            assert!((total - 1.0).abs() < 1e-12);
+            assert!((ev - partial.expected_score(category)).abs() < 1e-9);
+        }
+
+        // Sixes: 18 kept plus 6 per rolled six
+        assert!((partial.expected_score(Category::Sixes) - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        let keep = KeepPattern::from_counts([0, 0, 3, 0, 0, 0]).unwrap();
+        assert_eq!(format!("{keep}"), "keep 3x3");
+        assert_eq!(format!("{keep:#}"), "keep 3x3 (roll 2)");
+        assert_eq!(
+            format!("{:#}", KeepPattern::KEEP_NONE),
+            "keep none (roll 5)"
+        );
+    }
+
+    #[test]
+    fn test_same_effect() {
+        let config = DiceConfig::from_dice(&[2, 2, 4, 5, 6]);
+
+        // The placeholder KEEP_ALL keeps nothing here, since there are no 1s
+        assert!(KeepPattern::KEEP_ALL.same_effect(&KeepPattern::KEEP_NONE, &config));
+
+        let over = KeepPattern::from_counts([0, 3, 0, 0, 0, 0]).unwrap();
+        let pair = KeepPattern::from_counts([0, 2, 0, 0, 0, 0]).unwrap();
+        assert_eq!(over.normalized_for(&config), pair);
+        assert!(over.same_effect(&pair, &config));
+        assert!(!pair.same_effect(&KeepPattern::keep_all(&config), &config));
+    }
+
     #[test]
     fn test_keep_all() {
         let config = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
@@ -443,4 +636,48 @@ mod tests {
         assert!(partial.is_complete());
         assert_eq!(partial.dice_to_roll(), 0);
     }
+
+    #[test]
+    fn test_partial_roll_conditions_transitions() {
+        use crate::core::category::Category;
+        use crate::scoring::rules::score;
+        use crate::transition::table::TRANSITION_TABLE;
+
+        let sixes = KeepPattern::from_counts([0, 0, 0, 0, 0, 3]).unwrap();
+        let dicee_ev = |partial: &PartialDice| {
+            TRANSITION_TABLE.expected_value(partial, |config| {
+                score(config, Category::Dicee).score as f64
+            })
+        };
+
+        // Unobserved: both rerolled dice must come up 6
+        let unobserved = PartialDice::with_partial_roll(sixes, [0; 6], 2).unwrap();
+        assert_eq!(unobserved.dice_to_roll(), 2);
+
+        // One rerolled die is already showing a 6
+        let observed = PartialDice::with_partial_roll(sixes, [0, 0, 0, 0, 0, 1], 1).unwrap();
+        assert_eq!(observed.dice_to_roll(), 1);
+        assert_eq!(observed.kept_count(6), 4);
+
+        assert!((dicee_ev(&unobserved) - 50.0 / 36.0).abs() < 1e-9);
+        assert!((dicee_ev(&observed) - 50.0 / 6.0).abs() < 1e-9);
+        assert!(dicee_ev(&observed) > dicee_ev(&unobserved));
+
+        // Dice must total 5
+        assert_eq!(
+            PartialDice::with_partial_roll(sixes, [1, 0, 0, 0, 0, 0], 2),
+            Err(DiceeError::InvalidDiceCount(6))
+        );
+    }
+
+    #[test]
+    fn test_keep_pattern_serde_validates() {
+        let keep = KeepPattern::from_counts([0, 2, 0, 1, 0, 0]).unwrap();
+        let json = serde_json::to_string(&keep).unwrap();
+        assert_eq!(serde_json::from_str::<KeepPattern>(&json).unwrap(), keep);
+
+        // Six kept dice is not a valid pattern
+        assert!(serde_json::from_str::<KeepPattern>(r#"{"kept":[1,1,1,1,1,1]}"#).is_err());
+        assert!(serde_json::from_str::<KeepPattern>(r#"{"kept":[255,255,0,0,0,0]}"#).is_err());
+    }
 }
@@ -8,18 +8,32 @@
 //! - `category`: Scoring categories and category sets (Layer 2)
 //! - `turn`: Turn state and analysis (Layer 2)
 //! - `solver`: Dynamic programming solver (Layer 2)
+//! - `game_solver`: Full-game backward induction across turns
+//! - `game`: Game state between turns, and playing a turn (`rand` feature)
 
 pub mod category;
 pub mod config;
 pub mod error;
+pub mod game;
+pub mod game_solver;
 pub mod keep;
 pub mod solver;
 pub mod turn;
 
 // Re-exports for convenience
-pub use category::{Category, CategorySet, CategorySetIter};
-pub use config::{ConfigIndex, DiceConfig, ALL_CONFIGS, CONFIG_MULTIPLICITIES};
-pub use error::DiceeError;
+pub use category::{Category, CategorySet, CategorySetIter, Section};
+pub use config::{
+    ConfigIndex, DiceConfig, Shape, StraightDraw, ALL_CONFIGS, CONFIG_MULTIPLICITIES,
+    FRESH_ROLL_DISTRIBUTION,
+};
+pub use error::{DiceeError, ErrorClass};
+pub use game::GameState;
+pub use game_solver::GameSolver;
 pub use keep::{KeepPattern, PartialDice};
-pub use solver::{analyze_turn, quick_ev, TurnSolver};
-pub use turn::{Action, CategoryValue, TurnAnalysis, TurnState};
+pub use solver::{
+    analyze_turn, quick_ev, HintLevel, SharedSolverCache, SheetAnalyzer, TieBreak, TurnSolver,
+};
+pub use turn::{
+    Action, CategoryValue, CoachCard, DecisionBranch, DecisionNode, PolicyTable, TurnAnalysis,
+    TurnState,
+};
@@ -14,34 +14,152 @@
 //! - P(D'|K) is the transition probability
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 use crate::core::category::{Category, CategorySet};
-use crate::core::config::{ConfigIndex, DiceConfig};
+use crate::core::config::{ConfigIndex, DiceConfig, ALL_CONFIGS};
+use crate::core::error::DiceeError;
 use crate::core::keep::{KeepPattern, PartialDice};
-use crate::core::turn::{Action, CategoryValue, TurnAnalysis, TurnState};
-use crate::scoring::rules::score;
+use crate::core::turn::{
+    Action, CategoryValue, DecisionBranch, DecisionNode, PolicyTable, TurnAnalysis, TurnState,
+};
+use crate::scoring::rules::{score, upper_progress, ScoringRules};
 use crate::transition::table::TRANSITION_TABLE;
+use crate::types::{Category as TypesCategory, CategoryProbability, ProbabilityResult};
+use crate::{Dice, Result};
 
 // =============================================================================
 // CACHE KEY
 // =============================================================================
 
 /// Key for memoization cache.
+///
+/// The configuration is stored in its [packed](DiceConfig::pack) form, which
+/// is a few shifts to build. Its [index](DiceConfig::to_index) is closed-form
+/// too, but sums a table entry per die, so packing is still the cheaper key.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 struct CacheKey {
-    config_index: ConfigIndex,
+    packed_config: u32,
     rolls_remaining: u8,
     available: CategorySet,
+    rules: ScoringRules,
+    /// Bit pattern of the solver's discount factor.
+    discount_bits: u64,
 }
 
 impl CacheKey {
-    fn new(config: &DiceConfig, rolls_remaining: u8, available: &CategorySet) -> Self {
+    fn new(
+        config: &DiceConfig,
+        rolls_remaining: u8,
+        available: &CategorySet,
+        rules: ScoringRules,
+        discount: f64,
+    ) -> Self {
         Self {
-            config_index: config.to_index(),
+            packed_config: config.pack(),
             rolls_remaining,
             available: *available,
+            rules,
+            discount_bits: discount.to_bits(),
+        }
+    }
+}
+
+// =============================================================================
+// SHARED CACHE
+// =============================================================================
+
+/// A memoization cache that can be shared between solvers and threads.
+///
+/// Cloning the handle shares the underlying cache, so every
+/// [`TurnSolver::with_shared_cache`] built from it reuses the others' results.
+///
+/// # Example
+///
+/// ```rust
+/// use dicee_engine::core::solver::{SharedSolverCache, TurnSolver};
+///
+/// let cache = SharedSolverCache::new();
+/// let a = TurnSolver::with_shared_cache(cache.clone());
+/// let b = TurnSolver::with_shared_cache(cache.clone());
+/// // `a` and `b` now share memoized expected values
+/// ```
+#[derive(Clone, Default)]
+pub struct SharedSolverCache {
+    entries: Arc<Mutex<HashMap<CacheKey, f64>>>,
+}
+
+impl SharedSolverCache {
+    /// Creates a new, empty shared cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a shared cache with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(HashMap::with_capacity(capacity))),
         }
     }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Removes all cached entries for every solver sharing this cache.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<f64> {
+        self.lock().get(key).copied()
+    }
+
+    fn insert(&self, key: CacheKey, value: f64) {
+        self.lock().insert(key, value);
+    }
+
+    /// Locks the cache. Entries are plain values, so a poisoned lock is still usable.
+    fn lock(&self) -> MutexGuard<'_, HashMap<CacheKey, f64>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// =============================================================================
+// TIE BREAK
+// =============================================================================
+
+/// How the solver chooses between categories with equal immediate scores.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// Take the last tied category in set order.
+    #[default]
+    LastCategory,
+    /// When the tie includes upper categories, take the one whose score is
+    /// closest to (or furthest ahead of) upper bonus pace.
+    UpperPace,
+}
+
+// =============================================================================
+// HINT LEVEL
+// =============================================================================
+
+/// How much detail [`TurnSolver::hint`] includes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HintLevel {
+    /// Just the action, e.g. "Reroll".
+    #[default]
+    Low,
+    /// The action with its keep or score and expected value.
+    Medium,
+    /// The medium hint plus the reasoning and the top alternative categories.
+    High,
 }
 
 // =============================================================================
@@ -70,26 +188,143 @@ impl CacheKey {
 /// println!("Expected value: {:.2}", analysis.expected_value);
 /// ```
 pub struct TurnSolver {
-    /// Memoization cache for expected values.
-    cache: HashMap<CacheKey, f64>,
+    /// Memoization cache for expected values, possibly shared with other solvers.
+    cache: SharedSolverCache,
+    /// Margin by which rerolling must beat scoring to be recommended.
+    epsilon: f64,
+    /// House rules restricting where a roll may be scored.
+    rules: ScoringRules,
+    /// How equal immediate scores are resolved.
+    tie_break: TieBreak,
+    /// Factor applied to the value of every reroll.
+    discount: f64,
+    /// Callback invoked for every expected-value subproblem actually computed.
+    trace: Option<Box<TraceFn>>,
 }
 
+/// Callback receiving each subproblem a [`TurnSolver`] computes.
+type TraceFn = dyn Fn(ConfigIndex, u8, &CategorySet) + Send + Sync;
+
 impl TurnSolver {
+    /// Default reroll margin: absorbs floating-point noise in expected values
+    /// without masking any real advantage (scores are whole points).
+    pub const DEFAULT_EPSILON: f64 = 1e-9;
+
     /// Creates a new solver with an empty cache.
     pub fn new() -> Self {
-        Self {
-            cache: HashMap::new(),
-        }
+        Self::with_shared_cache(SharedSolverCache::new())
     }
 
     /// Creates a solver with a preallocated cache.
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_shared_cache(SharedSolverCache::with_capacity(capacity))
+    }
+
+    /// Creates a solver that memoizes into a cache shared with other solvers.
+    pub fn with_shared_cache(cache: SharedSolverCache) -> Self {
+        Self {
+            cache,
+            epsilon: Self::DEFAULT_EPSILON,
+            rules: ScoringRules::STANDARD,
+            tie_break: TieBreak::LastCategory,
+            discount: 1.0,
+            trace: None,
+        }
+    }
+
+    /// Sets the margin by which rerolling must beat the best immediate score
+    /// to be recommended.
+    ///
+    /// Larger values favor scoring in near-ties. Expected values themselves
+    /// are unaffected.
+    pub fn with_epsilon(self, epsilon: f64) -> Self {
+        Self { epsilon, ..self }
+    }
+
+    /// Sets the house rules, so the solver never scores a category they forbid.
+    ///
+    /// Illegal categories are excluded from every scoring decision, including
+    /// those reached after rerolling, so both recommendations and expected
+    /// values reflect the rules.
+    pub fn with_rules(self, rules: ScoringRules) -> Self {
+        Self { rules, ..self }
+    }
+
+    /// Sets how equal immediate scores are resolved.
+    ///
+    /// Tie-breaking only changes which category is recommended; expected
+    /// values are unaffected.
+    pub fn with_tie_break(self, tie_break: TieBreak) -> Self {
+        Self { tie_break, ..self }
+    }
+
+    /// Discounts every reroll's continuation value by `gamma`.
+    ///
+    /// Models impatience or time pressure: values below 1.0 make scoring now
+    /// relatively more attractive, and 1.0 is the standard solver. Expected
+    /// values reflect the discount.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gamma` is not in (0, 1].
+    pub fn with_discount(self, gamma: f64) -> Self {
+        assert!(
+            gamma > 0.0 && gamma <= 1.0,
+            "Discount must be in (0, 1], got {gamma}"
+        );
+        Self {
+            discount: gamma,
+            ..self
+        }
+    }
+
+    /// Reports every expected-value subproblem the solver computes to `f`.
+    ///
+    /// `f` receives the configuration, rolls remaining and open categories of
+    /// each state [`expected_value`](Self::expected_value) actually evaluates.
+    /// States answered from the cache or settled without a reroll search are
+    /// not reported, so each traced state is one unit of real work. No lock is
+    /// held while `f` runs, so it may query the solver; use interior
+    /// mutability to collect events. Solvers without a trace skip tracing
+    /// entirely.
+    pub fn with_trace(
+        self,
+        f: impl Fn(ConfigIndex, u8, &CategorySet) + Send + Sync + 'static,
+    ) -> Self {
         Self {
-            cache: HashMap::with_capacity(capacity),
+            trace: Some(Box::new(f)),
+            ..self
         }
     }
 
+    /// Returns the reroll margin used for recommendations.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Returns the house rules used for scoring decisions.
+    pub fn rules(&self) -> ScoringRules {
+        self.rules
+    }
+
+    /// Returns how equal immediate scores are resolved.
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// Returns the discount factor applied to reroll values.
+    pub fn discount(&self) -> f64 {
+        self.discount
+    }
+
+    /// Returns a handle to this solver's cache, for sharing with other solvers.
+    pub fn shared_cache(&self) -> SharedSolverCache {
+        self.cache.clone()
+    }
+
     /// Clears the memoization cache.
+    ///
+    /// A shared cache is cleared for every solver using it.
     pub fn clear_cache(&mut self) {
         self.cache.clear();
     }
@@ -101,22 +336,148 @@ impl TurnSolver {
 
     /// Computes complete analysis for a turn state.
     pub fn analyze(&self, state: &TurnState, available: &CategorySet) -> TurnAnalysis {
+        self.analyze_locked(state, available, KeepPattern::KEEP_NONE)
+    }
+
+    /// Returns advice for a turn state as text, at the requested level of detail.
+    ///
+    /// Each level extends the one below it, so a hint button can reveal more
+    /// on every press.
+    pub fn hint(&self, state: &TurnState, available: &CategorySet, level: HintLevel) -> String {
+        const ALTERNATIVES: usize = 3;
+
+        let analysis = self.analyze(state, available);
         if available.is_empty() {
-            // No categories available - shouldn't happen in normal play
-            return TurnAnalysis {
-                state: *state,
-                available: *available,
-                category_values: Vec::new(),
-                best_immediate: None,
-                continue_value: 0.0,
-                optimal_keep: KeepPattern::KEEP_NONE,
-                recommendation: Action::score(Category::Chance), // Fallback
-                expected_value: 0.0,
+            return "No categories left to score".to_string();
+        }
+
+        let action = match analysis.recommendation {
+            Action::Reroll { .. } => "Reroll".to_string(),
+            Action::Score { category } => format!("Score {category}"),
+        };
+        if level == HintLevel::Low {
+            return action;
+        }
+
+        let detail = match analysis.recommendation {
+            Action::Reroll { keep } => format!(": {keep:#}"),
+            Action::Score { category } => {
+                format!(" for {}", score(&state.config, category).score)
+            }
+        };
+        let summary = format!("{action}{detail} (EV {:.2})", analysis.expected_value);
+        if level == HintLevel::Medium {
+            return summary;
+        }
+
+        let best_now = analysis.best_immediate.map_or(0, |(_, points)| points);
+        let mut lines = vec![summary, format!("Best score now: {best_now}")];
+        if state.can_reroll() {
+            lines.push(format!(
+                "Best reroll: EV {:.2}; keeping everything: EV {:.2}",
+                analysis.continue_value, analysis.keep_all_value
+            ));
+        }
+        if let Some(p) = analysis.improvement_probability {
+            lines.push(format!(
+                "Chance the reroll improves on {best_now}: {:.0}%",
+                p * 100.0
+            ));
+        }
+        lines.push("Top categories:".to_string());
+        for cv in analysis.sorted_by_ev().into_iter().take(ALTERNATIVES) {
+            lines.push(format!(
+                "  {}: {} now, EV {:.2}",
+                cv.category, cv.immediate_score, cv.expected_value
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// Computes analysis for a turn state where some dice are locked by the player.
+    ///
+    /// The recommended reroll only considers keep patterns that include every
+    /// locked die. Locks apply to the current reroll decision only; later rolls
+    /// and the per-category expected values are unconstrained.
+    ///
+    /// Returns an error if `locked` is not valid for the state's configuration.
+    pub fn analyze_with_locks(
+        &self,
+        state: &TurnState,
+        available: &CategorySet,
+        locked: &KeepPattern,
+    ) -> Result<TurnAnalysis> {
+        locked.validate_for(&state.config)?;
+        Ok(self.analyze_locked(state, available, *locked))
+    }
+
+    /// Computes analysis for a turn committed to scoring in `category`.
+    ///
+    /// All reroll advice targets only `category`, as if it were the sole open
+    /// box, and the analysis reports that category's expected value under
+    /// optimal play. House rules do not apply, since the commitment is the
+    /// player's own.
+    pub fn analyze_for_category(&self, state: &TurnState, category: Category) -> TurnAnalysis {
+        let available = CategorySet::new().with(category);
+        let result = score(&state.config, category);
+        let immediate = result.score as f64;
+
+        let (continue_value, optimal_keep) = if state.can_reroll() {
+            self.best_keep_for_category(&state.config, state.rolls_remaining, category)
+        } else {
+            (immediate, KeepPattern::keep_all(&state.config))
+        };
+
+        let (recommendation, expected_value) =
+            if state.can_reroll() && continue_value > immediate + self.epsilon {
+                (Action::reroll(optimal_keep), continue_value)
+            } else {
+                (Action::score(category), immediate)
             };
+
+        TurnAnalysis {
+            state: *state,
+            available,
+            category_values: vec![CategoryValue {
+                category,
+                immediate_score: result.score,
+                is_valid: result.valid,
+                expected_value: continue_value.max(immediate),
+                is_optimal: true,
+            }],
+            best_immediate: Some((category, result.score)),
+            continue_value,
+            optimal_keep,
+            recommendation,
+            expected_value,
+            improvement_probability: improvement_probability(
+                state,
+                &available,
+                &ScoringRules::STANDARD,
+                &recommendation,
+            ),
+            keep_all_value: if state.can_reroll() {
+                self.discount * self.category_ev(&state.config, state.rolls_remaining - 1, category)
+            } else {
+                immediate
+            },
+        }
+    }
+
+    /// Shared analysis with the current reroll restricted to supersets of `locked`.
+    fn analyze_locked(
+        &self,
+        state: &TurnState,
+        available: &CategorySet,
+        locked: KeepPattern,
+    ) -> TurnAnalysis {
+        if available.is_empty() {
+            // No categories available - shouldn't happen in normal play
+            return empty_analysis(state, available);
         }
 
         // Compute immediate score for each available category
-        let category_values: Vec<CategoryValue> = available
+        let mut category_values: Vec<CategoryValue> = available
             .iter()
             .map(|cat| {
                 let result = score(&state.config, cat);
@@ -129,19 +490,17 @@ impl TurnSolver {
                     } else {
                         result.score as f64
                     },
+                    is_optimal: false,
                 }
             })
             .collect();
 
         // Find best immediate score
-        let best_immediate = category_values
-            .iter()
-            .max_by_key(|cv| cv.immediate_score)
-            .map(|cv| (cv.category, cv.immediate_score));
+        let best_immediate = self.best_immediate(&state.config, available);
 
         // Compute optimal continuation if rerolls available
         let (continue_value, optimal_keep) = if state.can_reroll() {
-            self.best_keep(&state.config, state.rolls_remaining, available)
+            self.best_keep(&state.config, state.rolls_remaining, available, locked)
         } else {
             (
                 best_immediate.map(|(_, s)| s as f64).unwrap_or(0.0),
@@ -151,14 +510,20 @@ impl TurnSolver {
 
         // Determine recommendation
         let best_immediate_value = best_immediate.map(|(_, s)| s as f64).unwrap_or(0.0);
+        let keep_all_value = if state.can_reroll() {
+            self.discount * self.expected_value(&state.config, state.rolls_remaining - 1, available)
+        } else {
+            best_immediate_value
+        };
 
         let (recommendation, expected_value) =
-            if state.can_reroll() && continue_value > best_immediate_value {
+            if state.can_reroll() && continue_value > best_immediate_value + self.epsilon {
                 (Action::reroll(optimal_keep), continue_value)
             } else {
                 let best_cat = best_immediate.map(|(c, _)| c).unwrap_or(Category::Chance);
                 (Action::score(best_cat), best_immediate_value)
             };
+        mark_optimal(&mut category_values, &recommendation);
 
         TurnAnalysis {
             state: *state,
@@ -169,22 +534,125 @@ impl TurnSolver {
             optimal_keep,
             recommendation,
             expected_value,
+            improvement_probability: improvement_probability(
+                state,
+                available,
+                &self.rules,
+                &recommendation,
+            ),
+            keep_all_value,
         }
     }
 
     /// Computes the expected value for a specific configuration, rolls remaining, and category.
     ///
     /// This answers: "If I continue optimally and eventually score in this category,
-    /// what's my expected score?"
+    /// what's my expected score?" Rerolls are discounted like in
+    /// [`expected_value`](Self::expected_value).
     pub fn category_ev(&self, config: &DiceConfig, rolls: u8, category: Category) -> f64 {
+        let immediate = score(config, category).score as f64;
         if rolls == 0 {
-            return score(config, category).score as f64;
+            return immediate;
         }
 
         // For a single category, we can compute EV directly
         // by finding the best keep pattern that maximizes EV for this category
         let (ev, _) = self.best_keep_for_category(config, rolls, category);
-        ev
+        ev.max(immediate)
+    }
+
+    /// Returns the best keep toward each available category, in set order.
+    ///
+    /// Entries line up with [`TurnAnalysis::category_values`]: each is the keep
+    /// that maximizes the expected score in that category alone, as valued by
+    /// [`category_ev`](Self::category_ev). With no rerolls left every entry is
+    /// `None`.
+    pub fn per_category_keeps(
+        &self,
+        state: &TurnState,
+        available: &CategorySet,
+    ) -> Vec<Option<KeepPattern>> {
+        available
+            .iter()
+            .map(|cat| {
+                state.can_reroll().then(|| {
+                    self.best_keep_for_category(&state.config, state.rolls_remaining, cat)
+                        .1
+                })
+            })
+            .collect()
+    }
+
+    /// Returns an "obvious" keep toward `category`: only dice that are clearly useful.
+    ///
+    /// This is a fast heuristic hint, not the EV-optimal keep:
+    ///
+    /// - Upper categories keep every die showing the face.
+    /// - n-of-a-kind and Dicee keep the most common face (the highest on ties).
+    /// - Full House keeps every pair or better, at most three of a face.
+    /// - Straights keep one die of each face in the longest run of
+    ///   consecutive faces (the highest on ties).
+    /// - Chance keeps the 5s and 6s.
+    pub fn safe_keep_for_category(config: &DiceConfig, category: Category) -> KeepPattern {
+        let counts = config.counts();
+        let mut kept = [0u8; 6];
+
+        match category {
+            Category::Ones
+            | Category::Twos
+            | Category::Threes
+            | Category::Fours
+            | Category::Fives
+            | Category::Sixes => {
+                let face = category.upper_face().unwrap_or(1);
+                kept[(face - 1) as usize] = config.count(face);
+            }
+            Category::ThreeOfAKind | Category::FourOfAKind | Category::Dicee => {
+                let face = config.mode_face();
+                kept[(face - 1) as usize] = config.count(face);
+            }
+            Category::FullHouse => {
+                for (k, &c) in kept.iter_mut().zip(counts) {
+                    if c >= 2 {
+                        *k = c.min(3);
+                    }
+                }
+            }
+            Category::SmallStraight | Category::LargeStraight => {
+                let (mut best_start, mut best_len) = (0, 0);
+                let mut start = 0;
+                for (face, &count) in counts.iter().enumerate() {
+                    if count == 0 {
+                        start = face + 1;
+                    } else if face + 1 - start >= best_len {
+                        (best_start, best_len) = (start, face + 1 - start);
+                    }
+                }
+                kept[best_start..best_start + best_len].fill(1);
+            }
+            Category::Chance => {
+                kept[4] = counts[4];
+                kept[5] = counts[5];
+            }
+        }
+
+        KeepPattern::from_counts(kept).expect("kept dice are a subset of the configuration")
+    }
+
+    /// Returns the highest score in `category` reachable with nonzero probability.
+    ///
+    /// This is the optimistic outcome, not an expectation. With no rolls left
+    /// it is the current score; a single reroll of every die can reach any
+    /// configuration, so with rolls left it is the best score over all of them.
+    pub fn max_reachable_score(config: &DiceConfig, rolls: u8, category: Category) -> u8 {
+        if rolls == 0 {
+            return score(config, category).score;
+        }
+        ALL_CONFIGS
+            .iter()
+            .map(|next| score(next, category).score)
+            .max()
+            .unwrap_or(0)
     }
 
     /// Computes the expected value of a turn state (max over all available categories).
@@ -195,102 +663,427 @@ impl TurnSolver {
 
         if rolls == 0 {
             // Must score now: return best immediate score
-            return available
-                .iter()
-                .map(|cat| score(config, cat).score)
-                .max()
-                .unwrap_or(0) as f64;
+            return self
+                .best_immediate(config, available)
+                .map_or(0.0, |(_, s)| s as f64);
+        }
+
+        let immediate_best = self.best_immediate(config, available).map_or(0, |(_, s)| s);
+
+        // No reroll can beat a score that is already the best attainable
+        if immediate_best >= max_attainable(available) {
+            return immediate_best as f64;
         }
 
         // Check cache
-        let key = CacheKey::new(config, rolls, available);
-        if let Some(&ev) = self.cache.get(&key) {
+        let key = CacheKey::new(config, rolls, available, self.rules, self.discount);
+        if let Some(ev) = self.cache.get(&key) {
             return ev;
         }
 
+        if let Some(trace) = &self.trace {
+            trace(config.to_index(), rolls, available);
+        }
+
         // Compute: max over scoring now vs rerolling
-        let immediate_best = available
-            .iter()
-            .map(|cat| score(config, cat).score)
-            .max()
-            .unwrap_or(0) as f64;
+        let immediate_best = immediate_best as f64;
 
-        let (reroll_ev, _) = self.best_keep(config, rolls, available);
+        let (reroll_ev, _) = self.best_keep(config, rolls, available, KeepPattern::KEEP_NONE);
 
-        immediate_best.max(reroll_ev)
+        let ev = immediate_best.max(reroll_ev);
+        self.cache.insert(key, ev);
+        ev
     }
 
-    /// Finds the best keep pattern and its expected value.
+    /// Returns what the second reroll is worth from a fresh roll: the expected
+    /// value with two rerolls left minus the value with one.
     ///
-    /// Returns (expected_value, optimal_keep_pattern).
-    fn best_keep(
+    /// Never negative; zero when the dice are already as good as they can get.
+    pub fn reroll_value(&self, config: &DiceConfig, available: &CategorySet) -> f64 {
+        self.expected_value(config, 2, available) - self.expected_value(config, 1, available)
+    }
+
+    /// Checks the Bellman equation for a state: the expected value must equal the
+    /// better of scoring now and the best reroll, within floating-point tolerance.
+    ///
+    /// The reroll line is recomputed from scratch through the transition table,
+    /// so this is a self-consistency check of the memoized recursion.
+    pub fn verify_bellman(&self, config: &DiceConfig, rolls: u8, available: &CategorySet) -> bool {
+        const TOLERANCE: f64 = 1e-9;
+
+        let ev = self.expected_value(config, rolls, available);
+        let immediate = self
+            .best_immediate(config, available)
+            .map_or(0.0, |(_, s)| s as f64);
+        if rolls == 0 || available.is_empty() {
+            return (ev - immediate).abs() <= TOLERANCE;
+        }
+
+        let (reroll, _) = best_keep_by(KeepPattern::iter_valid_for(config), |next_config| {
+            self.expected_value(next_config, rolls - 1, available)
+        });
+        (ev - immediate.max(self.discount * reroll)).abs() <= TOLERANCE
+    }
+
+    /// Returns every keep whose expected value is within `tol` of the best keep.
+    ///
+    /// More than one entry means the reroll decision is genuinely indifferent
+    /// between them. Keeps are in enumeration order, and with no rolls left
+    /// the only choice is keeping everything.
+    pub fn best_keeps(
         &self,
         config: &DiceConfig,
         rolls: u8,
         available: &CategorySet,
-    ) -> (f64, KeepPattern) {
+        tol: f64,
+    ) -> Vec<KeepPattern> {
         if rolls == 0 {
-            return (
-                self.expected_value(config, 0, available),
-                KeepPattern::keep_all(config),
-            );
+            return vec![KeepPattern::keep_all(config)];
         }
 
-        let mut best_ev = f64::NEG_INFINITY;
-        let mut best_keep = KeepPattern::KEEP_NONE;
-
-        for keep in KeepPattern::iter_valid_for(config) {
-            let partial = unsafe { PartialDice::new_unchecked(keep) };
+        let values: Vec<(KeepPattern, f64)> = KeepPattern::iter_valid_for(config)
+            .map(|keep| {
+                let partial = unsafe { PartialDice::new_unchecked(keep) };
+                let ev = TRANSITION_TABLE.expected_value(&partial, |next_config| {
+                    self.expected_value(next_config, rolls - 1, available)
+                });
+                (keep, self.discount * ev)
+            })
+            .collect();
+        let best = values
+            .iter()
+            .map(|&(_, ev)| ev)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        values
+            .into_iter()
+            .filter(|&(_, ev)| ev >= best - tol)
+            .map(|(keep, _)| keep)
+            .collect()
+    }
 
-            // Compute expected value over all reachable configs
+    /// Returns how much more keeping `keep_a` is worth than keeping `keep_b` (a − b).
+    ///
+    /// Each keep is valued as rerolling the other dice and then playing
+    /// optimally with one fewer roll. Returns an error if no rerolls remain or
+    /// either keep is not valid for `config`.
+    pub fn compare_keeps(
+        &self,
+        config: &DiceConfig,
+        rolls: u8,
+        available: &CategorySet,
+        keep_a: &KeepPattern,
+        keep_b: &KeepPattern,
+    ) -> Result<f64> {
+        if rolls == 0 {
+            return Err(DiceeError::NoRollsRemaining);
+        }
+        let value = |keep: &KeepPattern| -> Result<f64> {
+            let partial = PartialDice::new(*config, *keep)?;
             let ev = TRANSITION_TABLE.expected_value(&partial, |next_config| {
                 self.expected_value(next_config, rolls - 1, available)
             });
+            Ok(self.discount * ev)
+        };
+        Ok(value(keep_a)? - value(keep_b)?)
+    }
 
-            if ev > best_ev {
-                best_ev = ev;
-                best_keep = keep;
-            }
+    /// Computes the expected number of rerolls taken before scoring under optimal play.
+    ///
+    /// Follows the policy recommended by [`analyze`](Self::analyze) through the
+    /// transition table. The result is 0 when scoring now is optimal and never
+    /// exceeds `rolls`.
+    pub fn expected_rerolls(&self, config: &DiceConfig, rolls: u8, available: &CategorySet) -> f64 {
+        if rolls == 0 || available.is_empty() {
+            return 0.0;
         }
 
-        (best_ev, best_keep)
+        let immediate = self
+            .best_immediate(config, available)
+            .map_or(0.0, |(_, s)| s as f64);
+        let (continue_value, keep) =
+            self.best_keep(config, rolls, available, KeepPattern::KEEP_NONE);
+        if continue_value <= immediate + self.epsilon {
+            return 0.0;
+        }
+
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+        1.0 + TRANSITION_TABLE.expected_value(&partial, |next_config| {
+            self.expected_rerolls(next_config, rolls - 1, available)
+        })
     }
 
-    /// Finds the best keep pattern for a specific category.
-    // Lint renamed in Rust 1.92: only_used_in_recursion -> self_only_used_in_recursion
+    /// Computes the guaranteed minimum score of a turn state under risk-averse play.
+    ///
+    /// This is a maximin backward induction: each keep is valued by its worst
+    /// reachable outcome rather than its expectation, and the best such keep is
+    /// compared against scoring now. The result never exceeds
+    /// [`expected_value`](Self::expected_value).
+    pub fn maximin_value(&self, config: &DiceConfig, rolls: u8, available: &CategorySet) -> f64 {
+        let immediate = |config: &DiceConfig| {
+            self.best_immediate(config, available)
+                .map_or(0.0, |(_, s)| s as f64)
+        };
+
+        if rolls == 0 || available.is_empty() {
+            return immediate(config);
+        }
+
+        // Guaranteed values of every config with one fewer roll remaining
+        let reroll =
+            |config: &DiceConfig, values: &[f64]| self.discount * worst_case_keep(config, values);
+        let mut values: Vec<f64> = ALL_CONFIGS.iter().map(immediate).collect();
+        for _ in 1..rolls {
+            values = ALL_CONFIGS
+                .iter()
+                .map(|next| immediate(next).max(reroll(next, &values)))
+                .collect();
+        }
+
+        immediate(config).max(reroll(config, &values))
+    }
+
+    /// Computes the expected value of naive greedy play, for comparison with optimal play.
+    ///
+    /// The greedy policy always keeps the dice contributing to the best
+    /// immediate category, as chosen by
+    /// [`safe_keep_for_category`](Self::safe_keep_for_category), rerolls the
+    /// rest while rolls remain, and then scores
+    /// the best available category. Its value is computed exactly over the
+    /// transition table, and never exceeds [`expected_value`](Self::expected_value).
     #[allow(unknown_lints)]
     #[allow(clippy::only_used_in_recursion)]
     #[allow(clippy::self_only_used_in_recursion)]
-    fn best_keep_for_category(
-        &self,
-        config: &DiceConfig,
-        rolls: u8,
-        category: Category,
-    ) -> (f64, KeepPattern) {
-        if rolls == 0 {
-            return (
-                score(config, category).score as f64,
-                KeepPattern::keep_all(config),
-            );
+    pub fn greedy_policy_ev(&self, config: &DiceConfig, rolls: u8, available: &CategorySet) -> f64 {
+        let keep = greedy_keep(config, available);
+        if rolls == 0 || keep.total_kept() == 5 {
+            return self
+                .best_immediate(config, available)
+                .map_or(0.0, |(_, s)| s as f64);
         }
 
-        let mut best_ev = f64::NEG_INFINITY;
-        let mut best_keep = KeepPattern::KEEP_NONE;
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+        self.discount
+            * TRANSITION_TABLE.expected_value(&partial, |next_config| {
+                self.greedy_policy_ev(next_config, rolls - 1, available)
+            })
+    }
 
-        for keep in KeepPattern::iter_valid_for(config) {
-            let partial = unsafe { PartialDice::new_unchecked(keep) };
+    /// Builds the complete optimal policy for a category set.
+    ///
+    /// Every (configuration, rolls remaining) state is solved bottom-up, reusing
+    /// the values of the previous roll level instead of recursing per state.
+    /// Each stored action matches what [`analyze`](Self::analyze) recommends.
+    pub fn build_policy(&self, available: &CategorySet) -> PolicyTable {
+        let roll_states = TurnState::MAX_ROLLS as usize + 1;
+        let mut actions = vec![Action::score(Category::Chance); ConfigIndex::COUNT * roll_states];
 
-            // Compute expected value for this category over all reachable configs
-            let ev = TRANSITION_TABLE.expected_value(&partial, |next_config| {
-                if rolls == 1 {
-                    score(next_config, category).score as f64
+        if available.is_empty() {
+            return PolicyTable::new(*available, actions);
+        }
+
+        // Values of each config with one fewer roll remaining
+        let mut previous = [0.0f64; ConfigIndex::COUNT];
+
+        for rolls in 0..=TurnState::MAX_ROLLS {
+            let mut current = [0.0f64; ConfigIndex::COUNT];
+
+            for index in ConfigIndex::iter_all() {
+                let config = DiceConfig::from_index(index);
+                let (best_cat, best_score) = self
+                    .best_immediate(&config, available)
+                    .expect("non-empty category set has a best category");
+                let immediate = best_score as f64;
+
+                let (action, value) = if rolls > 0 {
+                    let keeps = KeepPattern::iter_valid_for(&config);
+                    let (continue_value, keep) = best_keep_by(keeps, |next_config| {
+                        previous[next_config.to_index().as_usize()]
+                    });
+                    let continue_value = self.discount * continue_value;
+                    if continue_value > immediate + self.epsilon {
+                        (Action::reroll(keep), continue_value)
+                    } else {
+                        (Action::score(best_cat), immediate)
+                    }
                 } else {
-                    self.best_keep_for_category(next_config, rolls - 1, category)
-                        .0
-                }
-            });
+                    (Action::score(best_cat), immediate)
+                };
 
-            if ev > best_ev {
+                actions[index.as_usize() * roll_states + rolls as usize] = action;
+                current[index.as_usize()] = value;
+            }
+
+            previous = current;
+        }
+
+        PolicyTable::new(*available, actions)
+    }
+
+    /// Expands the optimal line of play from a turn state into a tree.
+    ///
+    /// Each reroll node lists every reachable configuration with its
+    /// probability and the optimal decision from there, down to `depth`
+    /// rerolls; deeper reroll nodes are returned without children. An empty
+    /// category set yields a zero-point Chance leaf.
+    pub fn decision_tree(
+        &self,
+        config: &DiceConfig,
+        rolls: u8,
+        available: &CategorySet,
+        depth: u8,
+    ) -> DecisionNode {
+        let (category, score) = self
+            .best_immediate(config, available)
+            .unwrap_or((Category::Chance, 0));
+
+        if rolls > 0 && !available.is_empty() {
+            let (continue_value, keep) =
+                self.best_keep(config, rolls, available, KeepPattern::KEEP_NONE);
+            if continue_value > score as f64 + self.epsilon {
+                let children = if depth == 0 {
+                    Vec::new()
+                } else {
+                    let partial = unsafe { PartialDice::new_unchecked(keep) };
+                    TRANSITION_TABLE
+                        .get(&partial)
+                        .map(|entry| {
+                            let next = DiceConfig::from_index(entry.target);
+                            DecisionBranch {
+                                config: next,
+                                probability: entry.probability.get(),
+                                node: self.decision_tree(&next, rolls - 1, available, depth - 1),
+                            }
+                        })
+                        .collect()
+                };
+                return DecisionNode::Reroll {
+                    keep,
+                    expected_value: continue_value,
+                    children,
+                };
+            }
+        }
+
+        DecisionNode::Score { category, score }
+    }
+
+    /// Returns the best category the house rules allow, and its score.
+    fn best_immediate(
+        &self,
+        config: &DiceConfig,
+        available: &CategorySet,
+    ) -> Option<(Category, u8)> {
+        let legal = self.rules.legal_categories(config, available);
+        let (category, best) = best_immediate(config, &legal)?;
+        if self.tie_break == TieBreak::LastCategory || !category.is_upper() {
+            return Some((category, best));
+        }
+
+        // The default choice is an upper category, so other tied upper categories compete on pace
+        legal
+            .iter()
+            .filter(|&cat| cat.is_upper() && score(config, cat).score == best)
+            .max_by_key(|&cat| upper_progress(config, cat).map_or(i16::MIN, |(_, pace)| pace))
+            .map(|cat| (cat, best))
+    }
+
+    /// Finds the best keep pattern that includes `locked`, and its discounted expected value.
+    ///
+    /// Returns (expected_value, optimal_keep_pattern).
+    fn best_keep(
+        &self,
+        config: &DiceConfig,
+        rolls: u8,
+        available: &CategorySet,
+        locked: KeepPattern,
+    ) -> (f64, KeepPattern) {
+        if rolls == 0 {
+            return (
+                self.expected_value(config, 0, available),
+                KeepPattern::keep_all(config),
+            );
+        }
+
+        let keeps = KeepPattern::iter_supersets_for(config, locked);
+        let (ev, keep) = if rolls > 1 {
+            // Later rerolls can reach any configuration, so no keep can be bounded
+            best_keep_by(keeps, |next_config| {
+                self.expected_value(next_config, rolls - 1, available)
+            })
+        } else {
+            best_keep_bounded(keeps, available, |next_config| {
+                self.expected_value(next_config, 0, available)
+            })
+        };
+        (self.discount * ev, keep)
+    }
+
+    /// Probability that `category` is valid at the end of the turn when every
+    /// reroll targets it, following [`best_keep_for_category`](Self::best_keep_for_category).
+    ///
+    /// Results are memoized in `memo` by (configuration, rolls remaining,
+    /// category), so a memo can be shared across categories.
+    fn category_valid_probability(
+        &self,
+        config: &DiceConfig,
+        rolls: u8,
+        category: Category,
+        memo: &mut HashMap<(ConfigIndex, u8, Category), f64>,
+    ) -> f64 {
+        if rolls == 0 {
+            return if score(config, category).valid {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        let key = (config.to_index(), rolls, category);
+        if let Some(&probability) = memo.get(&key) {
+            return probability;
+        }
+
+        let (_, keep) = self.best_keep_for_category(config, rolls, category);
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+        let probability = TRANSITION_TABLE.expected_value(&partial, |next_config| {
+            self.category_valid_probability(next_config, rolls - 1, category, memo)
+        });
+        memo.insert(key, probability);
+        probability
+    }
+
+    /// Finds the best keep pattern for a specific category, and its discounted expected value.
+    // Lint renamed in Rust 1.92: only_used_in_recursion -> self_only_used_in_recursion
+    #[allow(unknown_lints)]
+    #[allow(clippy::only_used_in_recursion)]
+    #[allow(clippy::self_only_used_in_recursion)]
+    fn best_keep_for_category(
+        &self,
+        config: &DiceConfig,
+        rolls: u8,
+        category: Category,
+    ) -> (f64, KeepPattern) {
+        if rolls == 0 {
+            return (
+                score(config, category).score as f64,
+                KeepPattern::keep_all(config),
+            );
+        }
+
+        let mut best_ev = f64::NEG_INFINITY;
+        let mut best_keep = KeepPattern::KEEP_NONE;
+
+        for keep in KeepPattern::iter_valid_for(config) {
+            let partial = unsafe { PartialDice::new_unchecked(keep) };
+
+            // Compute expected value for this category over all reachable configs
+            let ev = self.discount
+                * TRANSITION_TABLE.expected_value(&partial, |next_config| {
+                    self.category_ev(next_config, rolls - 1, category)
+                });
+
+            if ev > best_ev {
                 best_ev = ev;
                 best_keep = keep;
             }
@@ -306,6 +1099,282 @@ impl Default for TurnSolver {
     }
 }
 
+// =============================================================================
+// SHEET ANALYZER
+// =============================================================================
+
+/// Analyzes one turn state against many candidate category sets.
+///
+/// The dice and rolls are fixed and only the category set varies between
+/// queries. Every query goes through the borrowed [`TurnSolver`], so answers
+/// honor its rules, tie-break, epsilon and discount, and every subproblem is
+/// memoized in its cache: repeated sets, and other analyzers or analyses on
+/// the same solver, reuse earlier work.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use dicee_engine::core::{Category, CategorySet, SheetAnalyzer, TurnSolver, TurnState};
+///
+/// let solver = TurnSolver::new();
+/// let analyzer = SheetAnalyzer::new(&solver, TurnState::from_dice(&[2, 2, 3, 5, 6], 2));
+///
+/// for category in Category::iter_all() {
+///     let ev = analyzer.expected_value(&CategorySet::new().with(category));
+///     println!("{category}: {ev:.2}");
+/// }
+/// ```
+pub struct SheetAnalyzer<'a> {
+    /// The solver answering every query.
+    solver: &'a TurnSolver,
+    /// The fixed turn state being analyzed.
+    state: TurnState,
+}
+
+impl<'a> SheetAnalyzer<'a> {
+    /// Creates an analyzer for a turn state backed by `solver`.
+    pub fn new(solver: &'a TurnSolver, state: TurnState) -> Self {
+        Self { solver, state }
+    }
+
+    /// Returns the turn state this analyzer was built for.
+    pub fn state(&self) -> &TurnState {
+        &self.state
+    }
+
+    /// Computes the expected value of the turn state for a category set.
+    ///
+    /// Matches [`TurnSolver::expected_value`].
+    pub fn expected_value(&self, available: &CategorySet) -> f64 {
+        self.solver
+            .expected_value(&self.state.config, self.state.rolls_remaining, available)
+    }
+
+    /// Computes complete analysis of the turn state for a category set.
+    ///
+    /// Matches [`TurnSolver::analyze`].
+    pub fn analyze(&self, available: &CategorySet) -> TurnAnalysis {
+        self.solver.analyze(&self.state, available)
+    }
+
+    /// Computes the expected value of the turn state for each category set, in order.
+    pub fn expected_values(&self, sets: &[CategorySet]) -> Vec<f64> {
+        sets.iter().map(|set| self.expected_value(set)).collect()
+    }
+}
+
+// =============================================================================
+// SEARCH HELPERS
+// =============================================================================
+
+/// Flags the category row matching the recommendation.
+///
+/// A reroll recommendation names no category, so the highest-EV row is
+/// flagged instead (the last one on ties, like [`best_immediate`]).
+fn mark_optimal(values: &mut [CategoryValue], recommendation: &Action) {
+    let chosen = match *recommendation {
+        Action::Score { category } => values.iter().position(|cv| cv.category == category),
+        Action::Reroll { .. } => values
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.expected_value.total_cmp(&b.expected_value))
+            .map(|(i, _)| i),
+    };
+    if let Some(i) = chosen {
+        values[i].is_optimal = true;
+    }
+}
+
+/// Analysis returned when no categories are available.
+fn empty_analysis(state: &TurnState, available: &CategorySet) -> TurnAnalysis {
+    TurnAnalysis {
+        state: *state,
+        available: *available,
+        category_values: Vec::new(),
+        best_immediate: None,
+        continue_value: 0.0,
+        optimal_keep: KeepPattern::KEEP_NONE,
+        recommendation: Action::score(Category::Chance), // Fallback
+        expected_value: 0.0,
+        improvement_probability: None,
+        keep_all_value: 0.0,
+    }
+}
+
+/// Probability that a recommended reroll beats the current best immediate score.
+///
+/// Returns `None` unless the recommendation is to reroll.
+fn improvement_probability(
+    state: &TurnState,
+    available: &CategorySet,
+    rules: &ScoringRules,
+    recommendation: &Action,
+) -> Option<f64> {
+    let Action::Reroll { keep } = recommendation else {
+        return None;
+    };
+    let legal_best = |config: &DiceConfig| {
+        best_immediate(config, &rules.legal_categories(config, available)).map_or(0, |(_, s)| s)
+    };
+    let current = legal_best(&state.config);
+    let partial = unsafe { PartialDice::new_unchecked(*keep) };
+    let probability = TRANSITION_TABLE
+        .conditional_probability(&partial, |next_config| legal_best(next_config) > current);
+    Some(probability.get())
+}
+
+/// Returns the highest-scoring available category and its score.
+///
+/// Ties resolve to the last category in set order, matching `max_by_key`.
+fn best_immediate(config: &DiceConfig, available: &CategorySet) -> Option<(Category, u8)> {
+    available
+        .iter()
+        .map(|cat| (cat, score(config, cat).score))
+        .max_by_key(|&(_, s)| s)
+}
+
+/// Returns the dice contributing to the best immediate category.
+///
+/// This is the [`safe_keep_for_category`](TurnSolver::safe_keep_for_category)
+/// hint for that category, so greedy play follows the same heuristic.
+fn greedy_keep(config: &DiceConfig, available: &CategorySet) -> KeepPattern {
+    best_immediate(config, available).map_or_else(
+        || KeepPattern::keep_all(config),
+        |(category, _)| TurnSolver::safe_keep_for_category(config, category),
+    )
+}
+
+/// Returns the highest score attainable in any available category.
+fn max_attainable(available: &CategorySet) -> u8 {
+    available
+        .iter()
+        .map(|cat| cat.max_score())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Finds the keep pattern among `keeps` maximizing the expected value of `value` after one roll.
+///
+/// Ties resolve to the first pattern in enumeration order.
+pub(crate) fn best_keep_by<I, F>(keeps: I, mut value: F) -> (f64, KeepPattern)
+where
+    I: IntoIterator<Item = KeepPattern>,
+    F: FnMut(&DiceConfig) -> f64,
+{
+    let mut best_ev = f64::NEG_INFINITY;
+    let mut best_keep = KeepPattern::KEEP_NONE;
+
+    for keep in keeps {
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+
+        // Compute expected value over all reachable configs
+        let ev = TRANSITION_TABLE.expected_value(&partial, &mut value);
+
+        if ev > best_ev {
+            best_ev = ev;
+            best_keep = keep;
+        }
+    }
+
+    (best_ev, best_keep)
+}
+
+/// Returns the best worst-case value over keep patterns after one roll.
+///
+/// Each keep is valued by the lowest `values` entry among its reachable configurations.
+fn worst_case_keep(config: &DiceConfig, values: &[f64]) -> f64 {
+    KeepPattern::iter_valid_for(config)
+        .map(|keep| {
+            let partial = unsafe { PartialDice::new_unchecked(keep) };
+            TRANSITION_TABLE
+                .get(&partial)
+                .map(|entry| values[entry.target.as_usize()])
+                .fold(f64::INFINITY, f64::min)
+        })
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Finds the best keep pattern for the last reroll, pruning keeps that can't win.
+///
+/// Keeps are tried with the most dice kept first, and a keep is skipped without
+/// evaluating its transitions when [`keep_score_bound`] shows no outcome can
+/// reach the current best. `value` must be the immediate score of the rolled
+/// configuration. The result, including tie-breaking, matches [`best_keep_by`].
+#[allow(clippy::float_cmp)] // Exact ties must resolve as in enumeration order
+fn best_keep_bounded<I, F>(keeps: I, available: &CategorySet, mut value: F) -> (f64, KeepPattern)
+where
+    I: IntoIterator<Item = KeepPattern>,
+    F: FnMut(&DiceConfig) -> f64,
+{
+    let mut ordered: Vec<(usize, KeepPattern)> = keeps.into_iter().enumerate().collect();
+    ordered.sort_by_key(|(_, keep)| std::cmp::Reverse(keep.total_kept()));
+
+    let mut best_ev = f64::NEG_INFINITY;
+    let mut best_keep = KeepPattern::KEEP_NONE;
+    let mut best_position = usize::MAX;
+
+    for (position, keep) in ordered {
+        // Margin guards against rounding in expected values that reach the bound
+        if keep_score_bound(&keep, available) as f64 + 1e-9 < best_ev {
+            continue;
+        }
+
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+        let ev = TRANSITION_TABLE.expected_value(&partial, &mut value);
+
+        // Ties resolve to the earliest pattern in enumeration order
+        if ev > best_ev || (ev == best_ev && position < best_position) {
+            best_ev = ev;
+            best_keep = keep;
+            best_position = position;
+        }
+    }
+
+    (best_ev, best_keep)
+}
+
+/// Returns an upper bound on the best immediate score of any configuration
+/// reachable by rolling the dice not in `keep`.
+fn keep_score_bound(keep: &KeepPattern, available: &CategorySet) -> u8 {
+    let kept = keep.counts();
+    let to_roll = keep.dice_to_roll();
+    let kept_sum: u8 = (1..=6).zip(kept).map(|(face, &count)| face * count).sum();
+    let distinct = kept.iter().filter(|&&count| count > 0).count();
+    let max_kept = kept.iter().copied().max().unwrap_or(0);
+
+    // Whether the rolled dice can fill every missing face of the run [start, start + len)
+    let run_reachable = |start: usize, len: usize| {
+        let present = kept[start..start + len].iter().filter(|&&c| c > 0).count();
+        len - present <= to_roll as usize
+    };
+
+    available
+        .iter()
+        .map(|category| match category {
+            Category::ThreeOfAKind | Category::FourOfAKind | Category::Chance => {
+                kept_sum + 6 * to_roll
+            }
+            Category::FullHouse if distinct <= 2 && max_kept <= 3 => 25,
+            Category::SmallStraight if (0..3).any(|start| run_reachable(start, 4)) => 30,
+            Category::LargeStraight
+                if max_kept <= 1 && (0..2).any(|start| run_reachable(start, 5)) =>
+            {
+                40
+            }
+            Category::Dicee if distinct <= 1 => 50,
+            Category::FullHouse
+            | Category::SmallStraight
+            | Category::LargeStraight
+            | Category::Dicee => 0,
+            upper => {
+                let face = upper.upper_face().expect("remaining categories are upper");
+                (kept[face as usize - 1] + to_roll) * face
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
 // =============================================================================
 // CONVENIENCE FUNCTIONS
 // =============================================================================
@@ -318,6 +1387,52 @@ pub fn analyze_turn(state: &TurnState, available: &CategorySet) -> TurnAnalysis
     solver.analyze(state, available)
 }
 
+/// Computes per-category probabilities and expected values in the legacy result shape.
+///
+/// Each category is analyzed as if every remaining reroll targets it:
+/// `expected_value` matches [`TurnSolver::category_ev`] for the full number
+/// of rolls remaining, and `probability` is the chance the category is valid
+/// at the end of the turn under that play. The best category is the one with
+/// the highest expected value, ties resolving to the first.
+pub fn calculate_all(dice: &Dice, rolls_remaining: u8) -> ProbabilityResult {
+    let solver = TurnSolver::new();
+    let config = DiceConfig::from_dice(dice);
+    let mut memo = HashMap::new();
+
+    let categories: Vec<CategoryProbability> = TypesCategory::all()
+        .iter()
+        .map(|&legacy| {
+            let category =
+                Category::from_index(legacy as usize).expect("legacy categories share indices");
+            CategoryProbability {
+                category: legacy,
+                probability: solver.category_valid_probability(
+                    &config,
+                    rolls_remaining,
+                    category,
+                    &mut memo,
+                ),
+                expected_value: solver.category_ev(&config, rolls_remaining, category),
+                current_score: u16::from(score(&config, category).score),
+            }
+        })
+        .collect();
+
+    let best = categories.iter().fold(&categories[0], |best, c| {
+        if c.expected_value > best.expected_value {
+            c
+        } else {
+            best
+        }
+    });
+
+    ProbabilityResult {
+        best_category: best.category,
+        best_ev: best.expected_value,
+        categories,
+    }
+}
+
 /// Quick expected value computation for a turn state.
 pub fn quick_ev(dice: &[u8; 5], rolls: u8, available: &CategorySet) -> f64 {
     let solver = TurnSolver::new();
@@ -440,4 +1555,1076 @@ mod tests {
         assert!(analysis1.expected_value > 0.0);
         assert!(analysis2.expected_value > 0.0);
     }
+
+    #[test]
+    fn test_perfect_score_early_exit() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+        assert_eq!(max_attainable(&available), 50);
+
+        let dicee = DiceConfig::from_dice(&[2, 2, 2, 2, 2]);
+        assert!((solver.expected_value(&dicee, 2, &available) - 50.0).abs() < 1e-12);
+
+        // Large Straight is the best attainable once Dicee is gone
+        let no_dicee = available.without(Category::Dicee);
+        assert_eq!(max_attainable(&no_dicee), 40);
+        let straight = DiceConfig::from_dice(&[2, 3, 4, 5, 6]);
+        assert!((solver.expected_value(&straight, 2, &no_dicee) - 40.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_analyze_with_locks() {
+        let solver = TurnSolver::new();
+        let state = TurnState::from_dice(&[3, 3, 3, 3, 6], 2);
+        let available = CategorySet::new().with(Category::Sixes);
+
+        // Unconstrained, chasing Sixes means dropping the 3s
+        let free = solver.analyze(&state, &available);
+        assert_eq!(free.optimal_keep.count(3), 0);
+
+        let locked = KeepPattern::from_counts([0, 0, 4, 0, 0, 0]).unwrap();
+        let analysis = solver
+            .analyze_with_locks(&state, &available, &locked)
+            .unwrap();
+        assert!(analysis.optimal_keep.includes(&locked));
+        if let Action::Reroll { keep } = analysis.recommendation {
+            assert_eq!(keep.count(3), 4);
+        } else {
+            panic!("Expected a reroll recommendation");
+        }
+
+        let too_many = KeepPattern::from_counts([0, 0, 5, 0, 0, 0]).unwrap();
+        assert!(solver
+            .analyze_with_locks(&state, &available, &too_many)
+            .is_err());
+    }
+
+    #[test]
+    fn test_policy_matches_analyze() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+        let policy = solver.build_policy(&available);
+
+        assert_eq!(policy.len(), ConfigIndex::COUNT * 3);
+
+        for dice in [
+            [5, 5, 5, 5, 5],
+            [1, 2, 3, 4, 6],
+            [3, 3, 3, 4, 5],
+            [2, 2, 5, 5, 6],
+            [1, 1, 2, 4, 6],
+        ] {
+            for rolls in 0..=2 {
+                let state = TurnState::from_dice(&dice, rolls);
+                let analysis = solver.analyze(&state, &available);
+                assert_eq!(
+                    policy.action_for(&state),
+                    analysis.recommendation,
+                    "Policy disagrees with analyze for {:?} with {} rolls",
+                    dice,
+                    rolls
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_discounted_policy_matches_analyze() {
+        let solver = TurnSolver::new().with_discount(0.5);
+        let available = CategorySet::all();
+        let policy = solver.build_policy(&available);
+
+        for config in DiceConfig::iter_all() {
+            for rolls in 0..=2 {
+                let state = TurnState::new(config, rolls);
+                assert_eq!(
+                    policy.action_for(&state),
+                    solver.analyze(&state, &available).recommendation,
+                    "Policy disagrees with analyze for {config:?} with {rolls} rolls"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_policy_serde_roundtrip() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::new()
+            .with(Category::Sixes)
+            .with(Category::Dicee);
+        let policy = solver.build_policy(&available);
+
+        let json = serde_json::to_string(&policy).unwrap();
+        let restored: PolicyTable = serde_json::from_str(&json).unwrap();
+        assert_eq!(policy, restored);
+    }
+
+    #[test]
+    fn test_sheet_analyzer_matches_analyze() {
+        use crate::scoring::rules::RuleFlags;
+
+        let solver = TurnSolver::new()
+            .with_rules(ScoringRules::new(RuleFlags::NO_ZERO_LOWER))
+            .with_tie_break(TieBreak::UpperPace)
+            .with_discount(0.8);
+        let category_sets = [
+            CategorySet::all(),
+            CategorySet::upper_only(),
+            CategorySet::lower_only(),
+            CategorySet::new().with(Category::Chance),
+            CategorySet::new()
+                .with(Category::Twos)
+                .with(Category::LargeStraight),
+            CategorySet::EMPTY,
+        ];
+
+        for rolls in 0..=2 {
+            let state = TurnState::from_dice(&[2, 2, 3, 5, 6], rolls);
+            let analyzer = SheetAnalyzer::new(&solver, state);
+            let values = analyzer.expected_values(&category_sets);
+
+            for (available, &value) in category_sets.iter().zip(&values) {
+                let expected = solver.analyze(&state, available);
+                let actual = analyzer.analyze(available);
+
+                assert_eq!(actual.recommendation, expected.recommendation);
+                assert_eq!(actual.optimal_keep, expected.optimal_keep);
+                assert_eq!(actual.best_immediate, expected.best_immediate);
+                assert!((actual.expected_value - expected.expected_value).abs() < 1e-9);
+                assert!((actual.continue_value - expected.continue_value).abs() < 1e-9);
+                assert!(
+                    (value
+                        - TurnSolver::new()
+                            .with_discount(0.8)
+                            .with_rules(solver.rules())
+                            .expected_value(&state.config, rolls, available))
+                    .abs()
+                        < 1e-9
+                );
+
+                assert_eq!(actual.category_values.len(), expected.category_values.len());
+                for (a, e) in actual.category_values.iter().zip(&expected.category_values) {
+                    assert_eq!(a.category, e.category);
+                    assert_eq!(a.immediate_score, e.immediate_score);
+                    assert!((a.expected_value - e.expected_value).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_maximin_value() {
+        let solver = TurnSolver::new();
+
+        // A sure thing is worth exactly its immediate score
+        let dicee = DiceConfig::from_dice(&[5, 5, 5, 5, 5]);
+        assert_eq!(solver.maximin_value(&dicee, 2, &CategorySet::all()), 50.0);
+
+        // Rerolling can't guarantee a straight, though it has positive EV
+        let draw = DiceConfig::from_dice(&[1, 2, 3, 4, 6]);
+        let straight = CategorySet::new().with(Category::LargeStraight);
+        assert_eq!(solver.maximin_value(&draw, 2, &straight), 0.0);
+        assert!(solver.expected_value(&draw, 2, &straight) > 0.0);
+
+        // Any reroll can come up all ones, so Chance never improves on its sum
+        let chance = CategorySet::new().with(Category::Chance);
+        for config in ALL_CONFIGS.iter() {
+            assert_eq!(
+                solver.maximin_value(config, 2, &chance),
+                config.sum() as f64
+            );
+        }
+
+        // The worst case is bounded by the immediate score and the expected value
+        let available = CategorySet::all();
+        for dice in [[1, 1, 3, 5, 6], [2, 2, 4, 5, 6], [3, 3, 3, 4, 5]] {
+            let config = DiceConfig::from_dice(&dice);
+            let maximin = solver.maximin_value(&config, 2, &available);
+            let immediate = solver.maximin_value(&config, 0, &available);
+            assert!(maximin >= immediate);
+            assert!(maximin <= solver.expected_value(&config, 2, &available) + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_epsilon_ignores_marginal_reroll() {
+        // Keeping 1-2-3-4 for a large straight is worth 30 + 40/6 ≈ 31.67,
+        // a 1.67 point edge over scoring the small straight now
+        let state = TurnState::from_dice(&[1, 2, 3, 4, 6], 1);
+        let available = CategorySet::new()
+            .with(Category::SmallStraight)
+            .with(Category::LargeStraight);
+
+        let default = TurnSolver::new();
+        assert_eq!(default.epsilon(), TurnSolver::DEFAULT_EPSILON);
+        assert!(default.analyze(&state, &available).should_reroll());
+
+        let cautious = TurnSolver::new().with_epsilon(5.0);
+        let analysis = cautious.analyze(&state, &available);
+        assert_eq!(
+            analysis.recommendation,
+            Action::score(Category::SmallStraight)
+        );
+        assert_eq!(analysis.expected_value, 30.0);
+
+        // The policy table honors the same margin
+        let policy = cautious.build_policy(&available);
+        assert_eq!(policy.action_for(&state), analysis.recommendation);
+    }
+
+    #[test]
+    fn test_greedy_policy_ev() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+
+        for dice in [
+            [1, 1, 3, 5, 6],
+            [2, 2, 4, 5, 6],
+            [3, 3, 3, 4, 5],
+            [1, 2, 3, 4, 6],
+            [6, 6, 5, 5, 1],
+        ] {
+            let config = DiceConfig::from_dice(&dice);
+            for rolls in 0..=2 {
+                let greedy = solver.greedy_policy_ev(&config, rolls, &available);
+                let optimal = solver.expected_value(&config, rolls, &available);
+                assert!(
+                    greedy <= optimal + 1e-9,
+                    "Greedy {greedy:.4} beats optimal {optimal:.4} for {dice:?} with {rolls} rolls"
+                );
+            }
+        }
+
+        // Where scoring now is optimal, greedy keeps everything and matches
+        for dice in [[5, 5, 5, 5, 5], [2, 3, 4, 5, 6]] {
+            let config = DiceConfig::from_dice(&dice);
+            let greedy = solver.greedy_policy_ev(&config, 2, &available);
+            let optimal = solver.expected_value(&config, 2, &available);
+            assert!((greedy - optimal).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bounded_keep_matches_exhaustive() {
+        let solver = TurnSolver::new();
+        let category_sets = [
+            CategorySet::all(),
+            CategorySet::upper_only(),
+            CategorySet::lower_only(),
+            CategorySet::new()
+                .with(Category::Chance)
+                .with(Category::LargeStraight),
+            CategorySet::new()
+                .with(Category::Dicee)
+                .with(Category::Ones),
+        ];
+
+        for available in &category_sets {
+            for config in ALL_CONFIGS.iter() {
+                let pruned = solver.best_keep(config, 1, available, KeepPattern::KEEP_NONE);
+                let exhaustive = best_keep_by(KeepPattern::iter_valid_for(config), |next| {
+                    solver.expected_value(next, 0, available)
+                });
+                assert_eq!(
+                    pruned, exhaustive,
+                    "Mismatch for {config:?} with {available}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_keep_score_bound_is_upper_bound() {
+        let available = CategorySet::all();
+        for config in ALL_CONFIGS.iter() {
+            for keep in KeepPattern::iter_valid_for(config) {
+                let bound = keep_score_bound(&keep, &available);
+                let partial = unsafe { PartialDice::new_unchecked(keep) };
+                for entry in TRANSITION_TABLE.get(&partial) {
+                    let next = DiceConfig::from_index(entry.target);
+                    let (_, best) = best_immediate(&next, &available).unwrap();
+                    assert!(best <= bound, "{keep:?} reaches {best} above bound {bound}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_expected_rerolls() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+
+        // A Dicee scores immediately
+        let dicee = DiceConfig::from_dice(&[3, 3, 3, 3, 3]);
+        assert_eq!(solver.expected_rerolls(&dicee, 2, &available), 0.0);
+
+        // A weak opening rerolls at least once, and at most twice
+        let junk = DiceConfig::from_dice(&[1, 1, 2, 4, 6]);
+        let rerolls = solver.expected_rerolls(&junk, 2, &available);
+        assert!((1.0..=2.0).contains(&rerolls), "Expected rerolls {rerolls}");
+        assert!(solver.expected_rerolls(&junk, 1, &available) <= 1.0);
+        assert_eq!(solver.expected_rerolls(&junk, 0, &available), 0.0);
+    }
+
+    #[test]
+    fn test_shared_cache() {
+        let cache = SharedSolverCache::new();
+        let first = TurnSolver::with_shared_cache(cache.clone());
+        let second = TurnSolver::with_shared_cache(cache.clone());
+        let config = DiceConfig::from_dice(&[2, 2, 3, 5, 6]);
+        let available = CategorySet::all();
+
+        let ev = first.expected_value(&config, 2, &available);
+        let warmed = cache.len();
+        assert!(warmed > 0);
+        assert_eq!(second.cache_size(), warmed);
+
+        // The second solver is answered from the shared entries
+        assert_eq!(second.expected_value(&config, 2, &available), ev);
+        assert_eq!(cache.len(), warmed);
+
+        // An independent solver computes the same value from scratch
+        let fresh = TurnSolver::new();
+        assert_eq!(fresh.expected_value(&config, 2, &available), ev);
+
+        let mut first = first;
+        first.clear_cache();
+        assert!(cache.is_empty());
+        assert_eq!(second.cache_size(), 0);
+    }
+
+    #[test]
+    fn test_shared_cache_across_threads() {
+        let cache = SharedSolverCache::new();
+        let config = DiceConfig::from_dice(&[1, 3, 4, 5, 5]);
+        let available = CategorySet::lower_only();
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let solver = TurnSolver::with_shared_cache(cache.clone());
+                std::thread::spawn(move || solver.expected_value(&config, 2, &available))
+            })
+            .collect();
+        let evs: Vec<f64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(evs[0], evs[1]);
+        assert!(!cache.is_empty());
+    }
+
+    #[test]
+    fn test_improvement_probability() {
+        let solver = TurnSolver::new();
+
+        // Drawing to a large straight with one roll: only a 5 improves
+        let state = TurnState::from_dice(&[1, 2, 3, 4, 6], 1);
+        let available = CategorySet::new().with(Category::LargeStraight);
+        let analysis = solver.analyze(&state, &available);
+        assert!(analysis.should_reroll());
+        let p = analysis.improvement_probability.unwrap();
+        assert!((p - 1.0 / 6.0).abs() < 1e-9, "Improvement probability {p}");
+
+        // Scoring recommendations have no improvement probability
+        let dicee = TurnState::from_dice(&[2, 2, 2, 2, 2], 2);
+        let analysis = solver.analyze(&dicee, &CategorySet::all());
+        assert_eq!(analysis.improvement_probability, None);
+    }
+
+    #[test]
+    fn test_rules_forbid_zero() {
+        use crate::scoring::rules::RuleFlags;
+
+        let state = TurnState::from_dice(&[2, 3, 4, 6, 6], 0);
+        let available = CategorySet::new()
+            .with(Category::Ones)
+            .with(Category::FullHouse);
+
+        // Both boxes score zero; standard play scratches Full House
+        let standard = TurnSolver::new().analyze(&state, &available);
+        assert_eq!(standard.recommendation, Action::score(Category::FullHouse));
+
+        // Forbidding lower-section zeroes moves the scratch to Ones
+        let solver = TurnSolver::new().with_rules(ScoringRules::new(RuleFlags::NO_ZERO_LOWER));
+        let analysis = solver.analyze(&state, &available);
+        assert_eq!(analysis.recommendation, Action::score(Category::Ones));
+
+        // Restrictions can only lower the value of a turn
+        let config = DiceConfig::from_dice(&[2, 3, 4, 6, 6]);
+        let restricted = solver.expected_value(&config, 2, &available);
+        let unrestricted = TurnSolver::new().expected_value(&config, 2, &available);
+        assert!(restricted <= unrestricted + 1e-9);
+    }
+
+    #[test]
+    fn test_analyze_for_category() {
+        let solver = TurnSolver::new();
+
+        // Open-ended straight draw: keep 2-3-4-5 and chase a 1 or 6
+        let state = TurnState::from_dice(&[2, 3, 4, 5, 5], 2);
+        let analysis = solver.analyze_for_category(&state, Category::LargeStraight);
+
+        let run = KeepPattern::from_counts([0, 1, 1, 1, 1, 0]).unwrap();
+        assert_eq!(analysis.recommendation, Action::reroll(run));
+
+        // Completion chance over two rolls is 1 - (4/6)^2 = 5/9
+        let expected = 40.0 * 5.0 / 9.0;
+        assert!((analysis.expected_value - expected).abs() < 1e-9);
+        assert!((analysis.category_values[0].expected_value - expected).abs() < 1e-9);
+        assert!(
+            (solver.category_ev(&state.config, 2, Category::LargeStraight) - expected).abs() < 1e-9
+        );
+
+        // A completed straight is scored at once
+        let done = TurnState::from_dice(&[1, 2, 3, 4, 5], 2);
+        let analysis = solver.analyze_for_category(&done, Category::LargeStraight);
+        assert_eq!(
+            analysis.recommendation,
+            Action::score(Category::LargeStraight)
+        );
+    }
+
+    #[test]
+    fn test_upper_pace_tie_break() {
+        // Threes and Sixes both score 6, but 6 in Threes is only 3 below pace
+        let state = TurnState::from_dice(&[3, 3, 6, 1, 1], 0);
+        let available = CategorySet::new()
+            .with(Category::Threes)
+            .with(Category::Sixes);
+
+        let default = TurnSolver::new().analyze(&state, &available);
+        assert_eq!(default.recommendation, Action::score(Category::Sixes));
+
+        let solver = TurnSolver::new().with_tie_break(TieBreak::UpperPace);
+        assert_eq!(solver.tie_break(), TieBreak::UpperPace);
+        let analysis = solver.analyze(&state, &available);
+        assert_eq!(analysis.recommendation, Action::score(Category::Threes));
+        assert_eq!(analysis.best_immediate, Some((Category::Threes, 6)));
+        assert_eq!(analysis.expected_value, default.expected_value);
+    }
+
+    #[test]
+    fn test_calculate_all_multi_roll() {
+        let dice = [2, 2, 3, 5, 6];
+        let config = DiceConfig::from_dice(&dice);
+        let solver = TurnSolver::new();
+        let result = calculate_all(&dice, 2);
+        assert_eq!(result.categories.len(), 13);
+
+        for entry in &result.categories {
+            let category = Category::from_index(entry.category as usize).unwrap();
+            let two_roll = solver.category_ev(&config, 2, category);
+            let one_roll = solver.category_ev(&config, 1, category);
+            assert!((entry.expected_value - two_roll).abs() < 1e-9);
+            assert!(entry.expected_value + 1e-9 >= one_roll);
+            assert!((0.0..=1.0 + 1e-9).contains(&entry.probability));
+        }
+
+        // A second reroll makes a Dicee much more likely than a single-roll approximation says
+        let dicee = &result.categories[Category::Dicee.index()];
+        assert!(dicee.expected_value > solver.category_ev(&config, 1, Category::Dicee) + 1.0);
+        assert!(dicee.probability > 0.0);
+
+        // With no rolls left, validity is known
+        let now = calculate_all(&dice, 0);
+        assert_eq!(now.categories[Category::Chance.index()].probability, 1.0);
+        assert_eq!(now.categories[Category::FullHouse.index()].probability, 0.0);
+        assert_eq!(now.best_category, TypesCategory::Chance);
+        assert_eq!(now.best_ev, 18.0);
+    }
+
+    #[test]
+    fn test_calculate_all_matches_brute_force_single_roll() {
+        // Enumerate ordered outcomes of the rerolled dice, as base-6 digits
+        fn brute_force_ev(config: &DiceConfig, keep: KeepPattern, category: Category) -> f64 {
+            let to_roll = u32::from(keep.dice_to_roll());
+            let outcomes = 6u32.pow(to_roll);
+            let total: u32 = (0..outcomes)
+                .map(|mut digits| {
+                    let mut counts = *keep.counts();
+                    for _ in 0..to_roll {
+                        counts[(digits % 6) as usize] += 1;
+                        digits /= 6;
+                    }
+                    let next = DiceConfig::from_counts(counts).unwrap();
+                    u32::from(score(&next, category).score)
+                })
+                .sum();
+            debug_assert!(keep.is_valid_for(config));
+            f64::from(total) / f64::from(outcomes)
+        }
+
+        for dice in [[1, 2, 3, 4, 6], [3, 3, 3, 5, 5], [2, 2, 4, 6, 6]] {
+            let config = DiceConfig::from_dice(&dice);
+            let result = calculate_all(&dice, 1);
+            for entry in &result.categories {
+                let category = Category::from_index(entry.category as usize).unwrap();
+                let brute = KeepPattern::iter_valid_for(&config)
+                    .map(|keep| brute_force_ev(&config, keep, category))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                assert!(
+                    (entry.expected_value - brute).abs() < 1e-9,
+                    "{dice:?} {category}: {} vs {brute}",
+                    entry.expected_value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_per_category_keeps() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::new()
+            .with(Category::Fives)
+            .with(Category::Dicee)
+            .with(Category::Chance);
+
+        let state = TurnState::from_dice(&[5, 5, 5, 5, 2], 2);
+        let keeps = solver.per_category_keeps(&state, &available);
+        let analysis = solver.analyze(&state, &available);
+        assert_eq!(keeps.len(), analysis.category_values.len());
+
+        // Chasing Dicee keeps the quad and rerolls the odd die
+        let quad = KeepPattern::from_counts([0, 0, 0, 0, 4, 0]).unwrap();
+        assert_eq!(keeps[1], Some(quad));
+        assert_eq!(keeps[0], Some(quad));
+
+        let done = TurnState::from_dice(&[5, 5, 5, 5, 2], 0);
+        assert!(solver
+            .per_category_keeps(&done, &available)
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn test_verify_bellman() {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&[1, 2, 3, 4, 6]);
+        for rolls in 0..=2 {
+            assert!(solver.verify_bellman(&config, rolls, &CategorySet::all()));
+            assert!(solver.verify_bellman(&config, rolls, &CategorySet::EMPTY));
+        }
+    }
+
+    #[test]
+    fn test_compare_keeps() {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&[5, 5, 5, 6, 6]);
+        let all = CategorySet::all();
+        let keep_all = KeepPattern::keep_all(&config);
+        let keep_none = KeepPattern::KEEP_NONE;
+
+        // A high full house is worth keeping over rerolling everything
+        let diff = solver
+            .compare_keeps(&config, 2, &all, &keep_all, &keep_none)
+            .unwrap();
+        assert!(diff > 0.0, "Keep-all advantage {diff}");
+
+        let reversed = solver
+            .compare_keeps(&config, 2, &all, &keep_none, &keep_all)
+            .unwrap();
+        assert!((diff + reversed).abs() < 1e-9);
+
+        let quad_sixes = KeepPattern::from_counts([0, 0, 0, 0, 0, 4]).unwrap();
+        assert!(solver
+            .compare_keeps(&config, 2, &all, &quad_sixes, &keep_none)
+            .is_err());
+        assert_eq!(
+            solver.compare_keeps(&config, 0, &all, &keep_all, &keep_none),
+            Err(DiceeError::NoRollsRemaining)
+        );
+    }
+
+    #[test]
+    fn test_keep_all_value() {
+        let solver = TurnSolver::new();
+        let all = CategorySet::all();
+
+        for dice in [[1, 2, 3, 4, 6], [3, 3, 3, 5, 5], [2, 2, 4, 5, 6]] {
+            let state = TurnState::from_dice(&dice, 2);
+            let analysis = solver.analyze(&state, &all);
+            assert!(analysis.keep_all_value <= analysis.continue_value + 1e-9);
+
+            let sheet = SheetAnalyzer::new(&solver, state).analyze(&all);
+            assert!((sheet.keep_all_value - analysis.keep_all_value).abs() < 1e-9);
+        }
+
+        // Keeping a Dicee is optimal, so its keep-all value is the continue value
+        let state = TurnState::from_dice(&[6, 6, 6, 6, 6], 2);
+        let analysis = solver.analyze(&state, &all);
+        assert_eq!(analysis.optimal_keep, KeepPattern::keep_all(&state.config));
+        assert!((analysis.keep_all_value - analysis.continue_value).abs() < 1e-9);
+
+        // Small straight is the best score once the rolls run out
+        let done = TurnState::from_dice(&[1, 2, 3, 4, 6], 0);
+        assert!((solver.analyze(&done, &all).keep_all_value - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_packed_cache_matches_index_keyed_induction() {
+        let solver = TurnSolver::new();
+        let available: CategorySet = [
+            Category::Twos,
+            Category::FullHouse,
+            Category::SmallStraight,
+            Category::Chance,
+        ]
+        .into_iter()
+        .collect();
+
+        // Backward induction over tables keyed by configuration index, with no cache
+        let immediate: Vec<f64> = ALL_CONFIGS
+            .iter()
+            .map(|config| f64::from(solver.best_immediate(config, &available).unwrap().1))
+            .collect();
+        let mut values = immediate.clone();
+        for rolls in 0..=2 {
+            // One solver for every configuration, so a packing collision would show
+            for config in DiceConfig::iter_all() {
+                let cached = solver.expected_value(&config, rolls, &available);
+                let indexed = values[config.to_index().as_usize()];
+                assert!(
+                    (cached - indexed).abs() < 1e-9,
+                    "{config:?} with {rolls} rolls"
+                );
+            }
+
+            let previous = values;
+            values = ALL_CONFIGS
+                .iter()
+                .map(|config| {
+                    let keeps = KeepPattern::iter_valid_for(config);
+                    let (reroll, _) =
+                        best_keep_by(keeps, |next| previous[next.to_index().as_usize()]);
+                    reroll.max(immediate[config.to_index().as_usize()])
+                })
+                .collect();
+        }
+    }
+
+    #[test]
+    fn test_exactly_one_optimal_category() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all().without(Category::Chance);
+
+        for dice in [
+            [1, 2, 3, 4, 6],
+            [3, 3, 3, 5, 5],
+            [6, 6, 6, 6, 6],
+            [1, 1, 2, 4, 6],
+        ] {
+            for rolls in 0..=2 {
+                let state = TurnState::from_dice(&dice, rolls);
+                for analysis in [
+                    solver.analyze(&state, &available),
+                    SheetAnalyzer::new(&solver, state).analyze(&available),
+                ] {
+                    let optimal: Vec<_> = analysis
+                        .category_values
+                        .iter()
+                        .filter(|cv| cv.is_optimal)
+                        .collect();
+                    assert_eq!(optimal.len(), 1, "{dice:?} with {rolls} rolls");
+
+                    match analysis.recommendation {
+                        Action::Score { category } => assert_eq!(optimal[0].category, category),
+                        Action::Reroll { .. } => {
+                            let max = analysis
+                                .category_values
+                                .iter()
+                                .map(|cv| cv.expected_value)
+                                .fold(f64::NEG_INFINITY, f64::max);
+                            assert!((optimal[0].expected_value - max).abs() < 1e-12);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_safe_keep_for_category() {
+        let keep = |dice: [u8; 5], category| {
+            TurnSolver::safe_keep_for_category(&DiceConfig::from_dice(&dice), category)
+        };
+
+        assert_eq!(
+            keep([3, 3, 3, 5, 5], Category::ThreeOfAKind),
+            KeepPattern::from_counts([0, 0, 3, 0, 0, 0]).unwrap()
+        );
+        assert_eq!(
+            keep([1, 2, 3, 4, 4], Category::LargeStraight),
+            KeepPattern::from_counts([1, 1, 1, 1, 0, 0]).unwrap()
+        );
+        assert_eq!(
+            keep([1, 2, 4, 5, 6], Category::SmallStraight),
+            KeepPattern::from_counts([0, 0, 0, 1, 1, 1]).unwrap()
+        );
+        assert_eq!(
+            keep([2, 2, 2, 2, 6], Category::FullHouse),
+            KeepPattern::from_counts([0, 3, 0, 0, 0, 0]).unwrap()
+        );
+        assert_eq!(
+            keep([1, 4, 5, 6, 6], Category::Chance),
+            KeepPattern::from_counts([0, 0, 0, 0, 1, 2]).unwrap()
+        );
+        assert_eq!(
+            keep([1, 4, 5, 6, 6], Category::Ones),
+            KeepPattern::from_counts([1, 0, 0, 0, 0, 0]).unwrap()
+        );
+
+        for config in DiceConfig::iter_all() {
+            for category in Category::ALL {
+                let safe = TurnSolver::safe_keep_for_category(&config, category);
+                assert!(safe.is_valid_for(&config), "{config:?} {category:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_discount_favors_scoring() {
+        let available = CategorySet::all();
+        let count_scores = |solver: &TurnSolver| {
+            DiceConfig::iter_all()
+                .filter(|&config| {
+                    solver
+                        .analyze(&TurnState::new(config, 1), &available)
+                        .should_score()
+                })
+                .count()
+        };
+
+        let standard = TurnSolver::new();
+        let undiscounted = TurnSolver::new().with_discount(1.0);
+        let impatient = TurnSolver::new().with_discount(0.5);
+        assert!(count_scores(&impatient) > count_scores(&undiscounted));
+        assert_eq!(count_scores(&undiscounted), count_scores(&standard));
+
+        let config = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+        for rolls in 0..=2 {
+            let a = standard.expected_value(&config, rolls, &available);
+            let b = undiscounted.expected_value(&config, rolls, &available);
+            assert!((a - b).abs() < f64::EPSILON);
+            assert!(impatient.verify_bellman(&config, rolls, &available));
+            assert!(
+                impatient.greedy_policy_ev(&config, rolls, &available)
+                    <= impatient.expected_value(&config, rolls, &available) + 1e-9
+            );
+            assert!(
+                impatient.maximin_value(&config, rolls, &available)
+                    <= impatient.expected_value(&config, rolls, &available) + 1e-9
+            );
+        }
+
+        // Keeps are valued through the discounted continuation
+        let keep_all = KeepPattern::keep_all(&config);
+        let keep_none = KeepPattern::KEEP_NONE;
+        let full = standard
+            .compare_keeps(&config, 1, &available, &keep_all, &keep_none)
+            .unwrap();
+        let half = impatient
+            .compare_keeps(&config, 1, &available, &keep_all, &keep_none)
+            .unwrap();
+        assert!(full.abs() > 1e-6);
+        assert!((half - 0.5 * full).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_settings_combine() {
+        use crate::scoring::rules::RuleFlags;
+
+        let rules = ScoringRules::new(RuleFlags::NO_ZERO_LOWER);
+        let solver = TurnSolver::new()
+            .with_rules(rules)
+            .with_epsilon(2.0)
+            .with_tie_break(TieBreak::UpperPace)
+            .with_discount(0.9);
+
+        assert_eq!(solver.rules(), rules);
+        assert!((solver.epsilon() - 2.0).abs() < f64::EPSILON);
+        assert_eq!(solver.tie_break(), TieBreak::UpperPace);
+        assert!((solver.discount() - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_discounted_category_analysis() {
+        let state = TurnState::new(DiceConfig::from_dice(&[6, 6, 6, 6, 5]), 2);
+        let sixes = CategorySet::new().with(Category::Sixes);
+
+        // Chasing a fifth six is worth it at full value...
+        let patient = TurnSolver::new();
+        let analysis = patient.analyze_for_category(&state, Category::Sixes);
+        assert!(matches!(analysis.recommendation, Action::Reroll { .. }));
+
+        // ...but not when rerolls are worth half
+        let impatient = TurnSolver::new().with_discount(0.5);
+        let analysis = impatient.analyze_for_category(&state, Category::Sixes);
+        assert_eq!(analysis.recommendation, Action::score(Category::Sixes));
+        assert!(analysis.continue_value < 24.0);
+        assert!((analysis.expected_value - 24.0).abs() < 1e-9);
+
+        // Both agree with the full analysis of a sheet with only Sixes open
+        for solver in [&patient, &impatient] {
+            let single = solver.analyze_for_category(&state, Category::Sixes);
+            let full = solver.analyze(&state, &sixes);
+            assert_eq!(single.recommendation, full.recommendation);
+            assert!((single.expected_value - full.expected_value).abs() < 1e-9);
+            assert!((single.continue_value - full.continue_value).abs() < 1e-9);
+            assert!((single.keep_all_value - full.keep_all_value).abs() < 1e-9);
+            let ev = solver.category_ev(&state.config, 2, Category::Sixes);
+            assert!((ev - full.expected_value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Discount must be in (0, 1]")]
+    fn test_discount_rejects_nan() {
+        let _ = TurnSolver::new().with_discount(f64::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "Discount must be in (0, 1]")]
+    fn test_discount_rejects_above_one() {
+        let _ = TurnSolver::new().with_discount(1.5);
+    }
+
+    #[test]
+    fn test_max_reachable_score() {
+        let four_sixes = DiceConfig::from_dice(&[6, 6, 6, 6, 2]);
+        assert_eq!(
+            TurnSolver::max_reachable_score(&four_sixes, 1, Category::Dicee),
+            50
+        );
+        assert_eq!(
+            TurnSolver::max_reachable_score(&four_sixes, 0, Category::Dicee),
+            0
+        );
+
+        let junk = DiceConfig::from_dice(&[1, 2, 4, 5, 5]);
+        assert_eq!(
+            TurnSolver::max_reachable_score(&junk, 0, Category::Fives),
+            10
+        );
+        assert_eq!(
+            TurnSolver::max_reachable_score(&junk, 0, Category::Chance),
+            17
+        );
+        assert_eq!(
+            TurnSolver::max_reachable_score(&junk, 2, Category::Chance),
+            30
+        );
+    }
+
+    #[test]
+    fn test_hint_levels() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+
+        let state = TurnState::from_dice(&[6, 6, 6, 6, 2], 2);
+        let low = solver.hint(&state, &available, HintLevel::Low);
+        let medium = solver.hint(&state, &available, HintLevel::Medium);
+        let high = solver.hint(&state, &available, HintLevel::High);
+        assert_eq!(low, "Reroll");
+        assert!(medium.starts_with("Reroll: keep 4x6 (roll 1)"), "{medium}");
+        assert!(medium.contains("EV "));
+        assert!(high.starts_with(&medium));
+        assert!(high.contains("Top categories:"));
+        assert!(low.len() < medium.len() && medium.len() < high.len());
+
+        let done = TurnState::from_dice(&[2, 2, 3, 3, 3], 0);
+        assert_eq!(
+            solver.hint(&done, &available, HintLevel::Low),
+            "Score Full House"
+        );
+        assert_eq!(
+            solver.hint(&done, &available, HintLevel::Medium),
+            "Score Full House for 25 (EV 25.00)"
+        );
+    }
+
+    #[test]
+    fn test_best_keeps_reports_ties() {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&[2, 2, 5, 5, 1]);
+        let dicee = CategorySet::new().with(Category::Dicee);
+
+        // Chasing a Dicee, a pair of 2s is as good as a pair of 5s
+        let keeps = solver.best_keeps(&config, 2, &dicee, 1e-9);
+        let twos = KeepPattern::from_counts([0, 2, 0, 0, 0, 0]).unwrap();
+        let fives = KeepPattern::from_counts([0, 0, 0, 0, 2, 0]).unwrap();
+        assert_eq!(keeps, vec![twos, fives]);
+
+        let (best_ev, best_keep) = solver.best_keep(&config, 2, &dicee, KeepPattern::KEEP_NONE);
+        assert!(keeps.contains(&best_keep));
+        assert!(best_ev > 0.0);
+
+        // Fives break the symmetry
+        let keeps = solver.best_keeps(&config, 2, &dicee.with(Category::Fives), 1e-9);
+        assert_eq!(keeps.len(), 1);
+
+        let done = solver.best_keeps(&config, 0, &dicee, 1e-9);
+        assert_eq!(done, vec![KeepPattern::keep_all(&config)]);
+    }
+
+    #[test]
+    fn test_trace_shows_memoization() {
+        use std::collections::HashSet;
+
+        /// Subproblems the recursion would enter with no cache at all.
+        fn uncached_calls(config: &DiceConfig, rolls: u8) -> u64 {
+            if rolls == 0 {
+                return 1;
+            }
+            1 + KeepPattern::iter_valid_for(config)
+                .map(|keep| {
+                    let partial = PartialDice::new(*config, keep).unwrap();
+                    TRANSITION_TABLE
+                        .get(&partial)
+                        .map(|entry| {
+                            uncached_calls(&DiceConfig::from_index(entry.target), rolls - 1)
+                        })
+                        .sum::<u64>()
+                })
+                .sum::<u64>()
+        }
+
+        let visited = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&visited);
+        let solver = TurnSolver::new().with_trace(move |config, rolls, _| {
+            sink.lock().unwrap().push((config, rolls));
+        });
+
+        let config = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+        let available = CategorySet::all();
+        let traced = solver.expected_value(&config, 2, &available);
+        assert!((traced - TurnSolver::new().expected_value(&config, 2, &available)).abs() < 1e-12);
+
+        // Each computed state is reported once; cache hits are not reported
+        let computed = std::mem::take(&mut *visited.lock().unwrap());
+        let unique: HashSet<_> = computed.iter().copied().collect();
+        assert_eq!(computed.len(), unique.len());
+        assert!(unique.len() <= ConfigIndex::COUNT + 1);
+        assert!((unique.len() as u64) * 100 < uncached_calls(&config, 2));
+
+        solver.expected_value(&config, 2, &available);
+        assert_eq!(visited.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_trace_may_query_solver() {
+        let cache = SharedSolverCache::new();
+        let inner = TurnSolver::with_shared_cache(cache.clone());
+        let solver =
+            TurnSolver::with_shared_cache(cache).with_trace(move |index, rolls, available| {
+                // Re-entering a solver from the callback must not deadlock
+                let config = DiceConfig::from_index(index);
+                assert!(inner.expected_value(&config, rolls - 1, available) >= 0.0);
+            });
+
+        let config = DiceConfig::from_dice(&[2, 3, 3, 5, 6]);
+        let available = CategorySet::upper_only();
+        let ev = solver.expected_value(&config, 2, &available);
+        assert!((ev - TurnSolver::new().expected_value(&config, 2, &available)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reroll_value() {
+        let solver = TurnSolver::new();
+        let available = CategorySet::all();
+
+        for config in DiceConfig::iter_all() {
+            assert!(
+                solver.reroll_value(&config, &available) >= -1e-9,
+                "{config:?}"
+            );
+        }
+
+        let dicee = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+        let junk = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+        assert!(solver.reroll_value(&dicee, &available).abs() < 1e-12);
+        assert!(solver.reroll_value(&junk, &available) > 1.0);
+    }
+
+    #[test]
+    fn test_decision_tree() {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&[6, 6, 6, 6, 2]);
+        let available = CategorySet::all();
+
+        let state = TurnState::new(config, 2);
+        let analysis = solver.analyze(&state, &available);
+        let Action::Reroll { keep } = analysis.recommendation else {
+            panic!("expected a reroll");
+        };
+
+        let DecisionNode::Reroll {
+            keep: tree_keep,
+            expected_value,
+            children,
+        } = solver.decision_tree(&config, 2, &available, 1)
+        else {
+            panic!("expected a reroll node");
+        };
+        assert_eq!(tree_keep, keep);
+        assert!((expected_value - analysis.expected_value).abs() < 1e-9);
+
+        let total: f64 = children.iter().map(|branch| branch.probability).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        let weighted: f64 = children
+            .iter()
+            .map(|branch| branch.probability * branch.node.expected_value())
+            .sum();
+        assert!((weighted - expected_value).abs() < 1e-9);
+
+        // Depth exhausted one level down: reroll nodes carry no children
+        for branch in &children {
+            if let DecisionNode::Reroll { children, .. } = &branch.node {
+                assert_eq!(children.as_slice(), []);
+            }
+        }
+
+        let impatient = TurnSolver::new().with_discount(0.5);
+        let DecisionNode::Reroll {
+            expected_value,
+            children,
+            ..
+        } = impatient.decision_tree(&config, 2, &CategorySet::new().with(Category::Dicee), 1)
+        else {
+            panic!("expected a reroll node");
+        };
+        let weighted: f64 = children
+            .iter()
+            .map(|branch| branch.probability * branch.node.expected_value())
+            .sum();
+        assert!((0.5 * weighted - expected_value).abs() < 1e-9);
+
+        let yahtzee = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+        assert_eq!(
+            solver.decision_tree(&yahtzee, 2, &available, 3),
+            DecisionNode::Score {
+                category: Category::Dicee,
+                score: 50
+            }
+        );
+    }
+
+    #[test]
+    fn test_category_valid_probability_memoized() {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&[6, 6, 6, 6, 2]);
+        let mut memo = HashMap::new();
+
+        // Chasing a Dicee from four of a kind: 1 - (5/6)^2
+        let p = solver.category_valid_probability(&config, 2, Category::Dicee, &mut memo);
+        assert!((p - 11.0 / 36.0).abs() < 1e-12);
+        let entries = memo.len();
+        assert!(entries > 0);
+
+        // A second query is answered from the memo without new entries
+        let again = solver.category_valid_probability(&config, 2, Category::Dicee, &mut memo);
+        assert!((again - p).abs() < f64::EPSILON);
+        assert_eq!(memo.len(), entries);
+
+        let result = calculate_all(&[6, 6, 6, 6, 2], 2);
+        let dicee = &result.categories[TypesCategory::Dicee as usize];
+        assert!((dicee.probability - p).abs() < 1e-12);
+    }
 }
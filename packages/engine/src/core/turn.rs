@@ -6,8 +6,10 @@
 use serde::{Deserialize, Serialize};
 
 use super::category::{Category, CategorySet};
-use super::config::DiceConfig;
+use super::config::{ConfigIndex, DiceConfig};
+use super::error::DiceeError;
 use super::keep::KeepPattern;
+use crate::{Dice, Result};
 
 // =============================================================================
 // TURN STATE
@@ -67,7 +69,12 @@ impl TurnState {
 // =============================================================================
 
 /// An action the player can take.
+///
+/// Serializes in the flat form the WASM API uses, tagged by a lowercase
+/// `type`: `{"type":"score","category":"FullHouse"}` or
+/// `{"type":"reroll","keep":[0,0,3,0,0,0]}` with kept counts per face.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "ActionRepr", try_from = "ActionRepr")]
 pub enum Action {
     /// Score in a specific category.
     Score {
@@ -107,6 +114,38 @@ impl Action {
     }
 }
 
+/// Serialized form of an [`Action`], with the keep as raw counts.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ActionRepr {
+    Score { category: Category },
+    Reroll { keep: [u8; 6] },
+}
+
+impl From<Action> for ActionRepr {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Score { category } => Self::Score { category },
+            Action::Reroll { keep } => Self::Reroll {
+                keep: *keep.counts(),
+            },
+        }
+    }
+}
+
+impl TryFrom<ActionRepr> for Action {
+    type Error = DiceeError;
+
+    fn try_from(repr: ActionRepr) -> Result<Self> {
+        Ok(match repr {
+            ActionRepr::Score { category } => Self::Score { category },
+            ActionRepr::Reroll { keep } => Self::Reroll {
+                keep: KeepPattern::from_counts(keep)?,
+            },
+        })
+    }
+}
+
 // =============================================================================
 // CATEGORY VALUE
 // =============================================================================
@@ -123,6 +162,10 @@ pub struct CategoryValue {
     /// Expected value if we continue optimally and score here later.
     /// Only meaningful if rolls_remaining > 0.
     pub expected_value: f64,
+    /// Whether this is the row the solver would choose: the recommended
+    /// category, or the highest-EV category when the advice is to reroll.
+    #[serde(default)]
+    pub is_optimal: bool,
 }
 
 // =============================================================================
@@ -158,6 +201,17 @@ pub struct TurnAnalysis {
 
     /// Expected value of the recommended action.
     pub expected_value: f64,
+
+    /// For a reroll recommendation, the probability that the reroll raises the
+    /// best immediate score above the current one. `None` when scoring.
+    #[serde(default)]
+    pub improvement_probability: Option<f64>,
+
+    /// Expected value of keeping every die this roll and playing on with one
+    /// fewer roll: the cost of locking in the current dice early. Equals the
+    /// best immediate score when no rerolls remain.
+    #[serde(default)]
+    pub keep_all_value: f64,
 }
 
 impl TurnAnalysis {
@@ -175,7 +229,7 @@ impl TurnAnalysis {
     /// Returns the category values sorted by immediate score (descending).
     pub fn sorted_by_immediate(&self) -> Vec<&CategoryValue> {
         let mut sorted: Vec<_> = self.category_values.iter().collect();
-        sorted.sort_by(|a, b| b.immediate_score.cmp(&a.immediate_score));
+        sorted.sort_by_key(|cv| std::cmp::Reverse(cv.immediate_score));
         sorted
     }
 
@@ -190,6 +244,204 @@ impl TurnAnalysis {
     pub fn should_score(&self) -> bool {
         self.recommendation.is_score()
     }
+
+    /// Maps a reroll recommendation onto positions in the player's ordered dice.
+    ///
+    /// For each kept face, the leftmost dice showing that face are chosen, so
+    /// the result is deterministic. Positions are returned in ascending order.
+    ///
+    /// Returns `None` if the recommendation is to score, or if `original` is not
+    /// an ordering of the analyzed configuration.
+    pub fn keep_positions(&self, original: &Dice) -> Option<Vec<usize>> {
+        let Action::Reroll { keep } = self.recommendation else {
+            return None;
+        };
+        if DiceConfig::try_from_dice(original).ok()? != self.state.config {
+            return None;
+        }
+
+        let mut remaining = *keep.counts();
+        let positions = original
+            .iter()
+            .enumerate()
+            .filter_map(|(position, &face)| {
+                let left = &mut remaining[(face - 1) as usize];
+                (*left > 0).then(|| {
+                    *left -= 1;
+                    position
+                })
+            })
+            .collect();
+        Some(positions)
+    }
+}
+
+// =============================================================================
+// COACH CARD
+// =============================================================================
+
+/// A flat summary of a [`TurnAnalysis`] for a recommendation card.
+///
+/// Built by [`TurnAnalysis::coach_card`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CoachCard {
+    /// Recommended action: "score" or "reroll".
+    pub action: String,
+    /// The category to score in, or a description of the dice to keep.
+    pub target: String,
+    /// Expected value of the recommended action.
+    pub expected_value: f64,
+    /// For a reroll, the probability that it improves the best immediate score.
+    pub improvement_probability: Option<f64>,
+    /// The highest-EV category other than the recommended one.
+    pub alternative: Option<String>,
+    /// Expected value of the alternative category.
+    pub alternative_value: Option<f64>,
+}
+
+impl TurnAnalysis {
+    /// Assembles the recommendation into a [`CoachCard`].
+    pub fn coach_card(&self) -> CoachCard {
+        let (action, target) = match self.recommendation {
+            Action::Score { category } => ("score", category.to_string()),
+            Action::Reroll { keep } => ("reroll", format!("{keep:#}")),
+        };
+        let alternative = self.sorted_by_ev().into_iter().find(|cv| !cv.is_optimal);
+
+        CoachCard {
+            action: action.to_string(),
+            target,
+            expected_value: self.expected_value,
+            improvement_probability: self.improvement_probability,
+            alternative: alternative.map(|cv| cv.category.to_string()),
+            alternative_value: alternative.map(|cv| cv.expected_value),
+        }
+    }
+}
+
+// =============================================================================
+// DECISION TREE
+// =============================================================================
+
+/// One decision along the optimal line of a turn.
+///
+/// Built by [`TurnSolver::decision_tree`](super::solver::TurnSolver::decision_tree).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DecisionNode {
+    /// Scoring now is optimal.
+    Score {
+        /// The category to score in.
+        category: Category,
+        /// Points scored.
+        score: u8,
+    },
+    /// Rerolling is optimal.
+    Reroll {
+        /// The dice to keep.
+        keep: KeepPattern,
+        /// Expected value of the reroll: the probability-weighted value of
+        /// the children, times the solver's discount.
+        expected_value: f64,
+        /// Every reachable configuration and its follow-up decision.
+        /// Empty when the tree was cut off at this depth.
+        children: Vec<DecisionBranch>,
+    },
+}
+
+impl DecisionNode {
+    /// Returns the expected value of this decision.
+    pub fn expected_value(&self) -> f64 {
+        match self {
+            Self::Score { score, .. } => *score as f64,
+            Self::Reroll { expected_value, .. } => *expected_value,
+        }
+    }
+}
+
+/// A reroll outcome in a [`DecisionNode`] tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecisionBranch {
+    /// The configuration rolled.
+    pub config: DiceConfig,
+    /// Probability of rolling it.
+    pub probability: f64,
+    /// The optimal decision from there.
+    pub node: DecisionNode,
+}
+
+// =============================================================================
+// POLICY TABLE
+// =============================================================================
+
+/// The optimal action for every turn state under a fixed category set.
+///
+/// Built by [`TurnSolver::build_policy`](super::solver::TurnSolver::build_policy).
+/// Actions are stored densely, one per (configuration, rolls remaining) pair,
+/// so lookups never touch the solver.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "PolicyTableRepr")]
+pub struct PolicyTable {
+    /// The categories the policy was computed for.
+    pub available: CategorySet,
+    /// Actions indexed by `config_index * 3 + rolls_remaining`.
+    actions: Vec<Action>,
+}
+
+/// Unvalidated serialized form of a [`PolicyTable`].
+#[derive(Deserialize)]
+struct PolicyTableRepr {
+    available: CategorySet,
+    actions: Vec<Action>,
+}
+
+impl TryFrom<PolicyTableRepr> for PolicyTable {
+    type Error = DiceeError;
+
+    fn try_from(repr: PolicyTableRepr) -> Result<Self> {
+        if repr.actions.len() != ConfigIndex::COUNT * Self::ROLL_STATES {
+            return Err(DiceeError::InvalidPolicyLength(repr.actions.len()));
+        }
+        Ok(Self::new(repr.available, repr.actions))
+    }
+}
+
+impl PolicyTable {
+    /// Number of rolls-remaining values stored per configuration.
+    const ROLL_STATES: usize = TurnState::MAX_ROLLS as usize + 1;
+
+    /// Creates a policy table from a dense action vector.
+    pub(crate) fn new(available: CategorySet, actions: Vec<Action>) -> Self {
+        debug_assert_eq!(actions.len(), ConfigIndex::COUNT * Self::ROLL_STATES);
+        Self { available, actions }
+    }
+
+    /// Returns the optimal action for a configuration and rolls remaining.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rolls_remaining > 2`.
+    pub fn get(&self, config: ConfigIndex, rolls_remaining: u8) -> Action {
+        assert!(
+            rolls_remaining <= TurnState::MAX_ROLLS,
+            "At most 2 rerolls allowed"
+        );
+        self.actions[config.as_usize() * Self::ROLL_STATES + rolls_remaining as usize]
+    }
+
+    /// Returns the optimal action for a turn state.
+    pub fn action_for(&self, state: &TurnState) -> Action {
+        self.get(state.config.to_index(), state.rolls_remaining)
+    }
+
+    /// Returns the number of states covered by the table.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if the table covers no states.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
 }
 
 // =============================================================================
@@ -199,6 +451,38 @@ impl TurnAnalysis {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::solver::TurnSolver;
+
+    #[test]
+    fn test_turn_analysis_serde_roundtrip() {
+        let solver = TurnSolver::new();
+        let state = TurnState::from_dice(&[2, 3, 4, 5, 5], 1);
+        let analysis = solver.analyze(&state, &CategorySet::all());
+
+        let json = serde_json::to_string(&analysis).unwrap();
+        let restored: TurnAnalysis = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.state, analysis.state);
+        assert_eq!(restored.available, analysis.available);
+        assert_eq!(restored.optimal_keep, analysis.optimal_keep);
+        assert_eq!(restored.recommendation, analysis.recommendation);
+        assert_eq!(restored.best_immediate, analysis.best_immediate);
+        assert!((restored.expected_value - analysis.expected_value).abs() < 1e-9);
+        assert!((restored.continue_value - analysis.continue_value).abs() < 1e-9);
+        assert_eq!(
+            restored.category_values.len(),
+            analysis.category_values.len()
+        );
+        for (r, a) in restored
+            .category_values
+            .iter()
+            .zip(&analysis.category_values)
+        {
+            assert_eq!(r.category, a.category);
+            assert_eq!(r.immediate_score, a.immediate_score);
+            assert!((r.expected_value - a.expected_value).abs() < 1e-9);
+        }
+    }
 
     #[test]
     fn test_turn_state_creation() {
@@ -219,12 +503,66 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "At most 2 rerolls allowed")]
     fn test_invalid_rolls_remaining() {
         let config = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
         TurnState::new(config, 3); // Panic: max is 2
     }
 
+    #[test]
+    fn test_coach_card() {
+        let solver = TurnSolver::new();
+        let state = TurnState::from_dice(&[6, 6, 6, 6, 2], 2);
+        let card = solver.analyze(&state, &CategorySet::all()).coach_card();
+
+        assert_eq!(card.action, "reroll");
+        assert_eq!(card.target, "keep 4x6 (roll 1)");
+        assert!(card.expected_value > 0.0);
+        assert!(card.improvement_probability.is_some());
+        assert!(card.alternative.is_some());
+        assert!(card.alternative_value.unwrap() <= card.expected_value + 1e-9);
+
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(serde_json::from_str::<CoachCard>(&json).unwrap(), card);
+
+        let done = TurnState::from_dice(&[2, 2, 3, 3, 3], 0);
+        let only = CategorySet::new().with(Category::FullHouse);
+        let card = solver.analyze(&done, &only).coach_card();
+        assert_eq!(card.action, "score");
+        assert_eq!(card.target, "Full House");
+        assert_eq!(card.alternative, None);
+    }
+
+    #[test]
+    fn test_policy_deserialize_validates_length() {
+        let err =
+            serde_json::from_str::<PolicyTable>(r#"{"available":8191,"actions":[]}"#).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid policy table with 0 actions"));
+
+        let policy = TurnSolver::new().build_policy(&CategorySet::new().with(Category::Chance));
+        let json = serde_json::to_string(&policy).unwrap();
+        assert_eq!(serde_json::from_str::<PolicyTable>(&json).unwrap(), policy);
+    }
+
+    #[test]
+    fn test_action_serde_format() {
+        let score = Action::score(Category::FullHouse);
+        let json = serde_json::to_string(&score).unwrap();
+        assert_eq!(json, r#"{"type":"score","category":"FullHouse"}"#);
+        assert_eq!(serde_json::from_str::<Action>(&json).unwrap(), score);
+
+        let reroll = Action::reroll(KeepPattern::from_counts([0, 0, 3, 0, 0, 0]).unwrap());
+        let json = serde_json::to_string(&reroll).unwrap();
+        assert_eq!(json, r#"{"type":"reroll","keep":[0,0,3,0,0,0]}"#);
+        assert_eq!(serde_json::from_str::<Action>(&json).unwrap(), reroll);
+
+        assert!(
+            serde_json::from_str::<Action>(r#"{"type":"reroll","keep":[6,0,0,0,0,0]}"#).is_err()
+        );
+    }
+
     #[test]
     fn test_action_variants() {
         let score_action = Action::score(Category::Dicee);
@@ -236,4 +574,30 @@ mod tests {
         assert!(reroll_action.is_reroll());
         assert!(!reroll_action.is_score());
     }
+
+    #[test]
+    fn test_keep_positions() {
+        let solver = TurnSolver::new();
+        let dice = [5, 2, 5, 6, 5];
+        let state = TurnState::from_dice(&dice, 2);
+        let available = CategorySet::new().with(Category::Fives);
+
+        // Chasing fives keeps exactly the three 5s
+        let analysis = solver.analyze(&state, &available);
+        assert_eq!(analysis.keep_positions(&dice), Some(vec![0, 2, 4]));
+
+        // Keeping one of several duplicates picks the leftmost
+        let mut partial = analysis.clone();
+        partial.recommendation =
+            Action::reroll(KeepPattern::from_counts([0, 0, 0, 0, 1, 0]).unwrap());
+        assert_eq!(partial.keep_positions(&dice), Some(vec![0]));
+
+        // Dice that don't match the analyzed configuration are rejected
+        assert_eq!(analysis.keep_positions(&[1, 2, 3, 4, 5]), None);
+
+        // Scoring recommendations have no keep
+        let dicee = TurnState::from_dice(&[4, 4, 4, 4, 4], 2);
+        let scored = solver.analyze(&dicee, &CategorySet::all());
+        assert_eq!(scored.keep_positions(&[4, 4, 4, 4, 4]), None);
+    }
 }
@@ -0,0 +1,73 @@
+//! Helpers for ordered dice, the representation players see.
+//!
+//! The solver works on canonical [`DiceConfig`](crate::DiceConfig)s, but a
+//! game loop rolls physical dice in fixed positions. This module is only
+//! available with the `rand` feature.
+
+use rand::RngExt;
+
+use crate::Dice;
+
+/// Rerolls every die whose position is not kept, in place.
+///
+/// Kept positions are left untouched; every other position gets a fresh
+/// value in 1-6.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::dice::reroll;
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(1);
+/// let mut dice = [6, 6, 6, 2, 1];
+/// reroll(&mut dice, &[true, true, true, false, false], &mut rng);
+/// assert_eq!(&dice[..3], &[6, 6, 6]);
+/// ```
+pub fn reroll<R: rand::Rng + ?Sized>(dice: &mut Dice, keep: &[bool; 5], rng: &mut R) {
+    for (die, &kept) in dice.iter_mut().zip(keep) {
+        if !kept {
+            *die = rng.random_range(1..=6);
+        }
+    }
+}
+
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_reroll_keeps_positions() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(7);
+        let keep = [true, false, true, false, false];
+        let mut rerolled_values = [false; 6];
+
+        for _ in 0..200 {
+            let mut dice = [4, 4, 2, 4, 4];
+            reroll(&mut dice, &keep, &mut rng);
+
+            assert_eq!(dice[0], 4);
+            assert_eq!(dice[2], 2);
+            for &d in &[dice[1], dice[3], dice[4]] {
+                assert!((1..=6).contains(&d));
+                rerolled_values[d as usize - 1] = true;
+            }
+        }
+
+        // Every face turns up among the rerolled positions
+        assert!(rerolled_values.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn test_reroll_keep_all_is_noop() {
+        let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(7);
+        let mut dice = [1, 2, 3, 4, 5];
+        reroll(&mut dice, &[true; 5], &mut rng);
+        assert_eq!(dice, [1, 2, 3, 4, 5]);
+    }
+}
@@ -55,6 +55,8 @@
 // =============================================================================
 
 pub mod core; // Layer 0+ (config, error, keep, solver)
+#[cfg(feature = "rand")]
+pub mod dice; // Ordered dice helpers (rand feature)
 pub mod scoring; // Scoring rules (uses DiceConfig)
 pub mod transition; // Layer 1 (probability, transition table)
 pub mod types; // Public types (Category, ScoringResult)
@@ -69,8 +71,9 @@ use wasm_bindgen::prelude::*;
 
 // Solver types for new WASM API
 use core::category::CategorySet;
+use core::keep::KeepPattern;
 use core::solver::TurnSolver;
-use core::turn::TurnState;
+use core::turn::{TurnAnalysis, TurnState};
 use serde::Serialize;
 
 /// Result type alias for this crate (internal use).
@@ -93,6 +96,59 @@ pub fn validate_dice(dice: &Dice) -> Result<()> {
     Ok(())
 }
 
+/// Validates a dice array, reporting every out-of-range die at once.
+///
+/// Unlike [`validate_dice`], which stops at the first problem, this returns
+/// all `(position, value)` pairs outside 1-6, for highlighting form fields.
+pub fn validate_positions(dice: &Dice) -> std::result::Result<(), Vec<(usize, u8)>> {
+    let invalid: Vec<(usize, u8)> = dice
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, d)| !(1..=6).contains(d))
+        .collect();
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(invalid)
+    }
+}
+
+/// Parses dice from text such as `"3,3,3,5,5"` or `"3 3 3 5 5"`.
+///
+/// Values may be separated by commas, whitespace, or both. Exactly 5 values
+/// in the range 1-6 are required.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::parse_dice_str;
+///
+/// assert_eq!(parse_dice_str("3, 3, 3, 5, 5").unwrap(), [3, 3, 3, 5, 5]);
+/// assert!(parse_dice_str("1 2 3 4").is_err());
+/// ```
+pub fn parse_dice_str(s: &str) -> Result<Dice> {
+    let tokens: Vec<&str> = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if tokens.len() != 5 {
+        return Err(DiceeError::InvalidDiceCount(tokens.len()));
+    }
+
+    let mut dice = [0u8; 5];
+    for (i, token) in tokens.iter().enumerate() {
+        dice[i] = token.parse().map_err(|_| DiceeError::UnparseableDie {
+            token: (*token).to_string(),
+            position: i,
+        })?;
+    }
+
+    validate_dice(&dice)?;
+    Ok(dice)
+}
+
 // Re-export core types at crate root for convenience
 pub use core::config::{ConfigIndex, DiceConfig};
 pub use core::error::DiceeError as Error;
@@ -104,9 +160,42 @@ pub use core::error::DiceeError as Error;
 /// Get metadata for all categories (names, sections)
 #[wasm_bindgen]
 pub fn get_categories() -> std::result::Result<JsValue, JsValue> {
-    let categories: Vec<CategoryInfo> = Category::all().iter().map(|c| c.info()).collect();
+    serde_wasm_bindgen::to_value(&category_infos()).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Metadata for all categories, in index order.
+fn category_infos() -> Vec<CategoryInfo> {
+    Category::all().iter().map(|c| c.info()).collect()
+}
+
+/// JS-friendly outcome of rolling all five dice.
+#[derive(Serialize)]
+struct FreshRollJs {
+    /// The configuration as sorted dice.
+    dice: Dice,
+    /// Probability of rolling it.
+    probability: f64,
+}
+
+/// Get the probability of every configuration on a fresh roll of all five dice.
+///
+/// Returns the 252 configurations in index order, for charting roll shapes.
+#[wasm_bindgen]
+pub fn fresh_roll_distribution() -> std::result::Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(&fresh_roll_entries())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
 
-    serde_wasm_bindgen::to_value(&categories).map_err(|e| JsValue::from_str(&e.to_string()))
+/// Every configuration paired with its fresh-roll probability, in index order.
+fn fresh_roll_entries() -> Vec<FreshRollJs> {
+    core::ALL_CONFIGS
+        .iter()
+        .zip(core::FRESH_ROLL_DISTRIBUTION)
+        .map(|(config, probability)| FreshRollJs {
+            dice: config.to_dice(),
+            probability,
+        })
+        .collect()
 }
 
 // =============================================================================
@@ -124,6 +213,8 @@ struct CategoryAnalysisJs {
     is_valid: bool,
     /// Expected value if we continue optimally.
     expected_value: f64,
+    /// Whether this is the category the solver would choose.
+    is_optimal: bool,
 }
 
 /// JS-friendly turn analysis for WASM output.
@@ -209,9 +300,89 @@ pub fn analyze_turn(
     let state = TurnState::new(config, rolls_remaining);
     let available = CategorySet::from_bits(available_categories);
 
+    // Run solver
+    let solver = TurnSolver::new();
+    let analysis = solver.analyze(&state, &available);
+
+    serde_wasm_bindgen::to_value(&to_turn_analysis_js(&analysis))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Analyze the current turn with some dice locked by the player.
+///
+/// Same as [`analyze_turn`], but the recommended keep pattern always includes
+/// the locked dice. Locks apply to the current reroll only.
+///
+/// # Arguments
+///
+/// * `dice` - Array of 5 dice values (1-6)
+/// * `rolls_remaining` - Number of rerolls left (0, 1, or 2)
+/// * `available_categories` - Bitmask of available categories (0x1FFF = all 13)
+/// * `locked_mask` - Bitmask of dice positions that must be kept (bit i = `dice[i]`)
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - Dice array is not exactly 5 values
+/// - Dice values are not in range 1-6
+/// - `rolls_remaining` is greater than 2
+/// - `locked_mask` has bits set above bit 4
+#[wasm_bindgen]
+pub fn analyze_turn_with_locks(
+    dice: &[u8],
+    rolls_remaining: u8,
+    available_categories: u16,
+    locked_mask: u8,
+) -> std::result::Result<JsValue, JsValue> {
+    // Validate and parse dice
+    let dice = parse_dice(dice).map_err(JsValue::from_str)?;
+
+    // Validate rolls_remaining
+    if rolls_remaining > 2 {
+        return Err(JsValue::from_str("rolls_remaining must be 0, 1, or 2"));
+    }
+
+    let locked = locked_keep_from_mask(&dice, locked_mask).map_err(JsValue::from_str)?;
+
+    // Create solver inputs
+    let config = DiceConfig::from_dice(&dice);
+    let state = TurnState::new(config, rolls_remaining);
+    let available = CategorySet::from_bits(available_categories);
+
+    // Run solver (locks derived from the dice are always valid for the config)
+    let solver = TurnSolver::new();
+    let analysis = solver
+        .analyze_with_locks(&state, &available, &locked)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_wasm_bindgen::to_value(&to_turn_analysis_js(&analysis))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Convert a position mask of locked dice into a keep pattern.
+fn locked_keep_from_mask(
+    dice: &Dice,
+    locked_mask: u8,
+) -> std::result::Result<KeepPattern, &'static str> {
+    if locked_mask >= 1 << 5 {
+        return Err("locked_mask must only use bits 0-4");
+    }
+
+    let mut counts = [0u8; 6];
+    for (i, &d) in dice.iter().enumerate() {
+        if locked_mask & (1 << i) != 0 {
+            counts[(d - 1) as usize] += 1;
+        }
+    }
+
+    KeepPattern::from_counts(counts).map_err(|_| "locked dice exceed 5")
+}
+
+/// Convert a solver analysis to the JS-friendly format.
+fn to_turn_analysis_js(analysis: &TurnAnalysis) -> TurnAnalysisJs {
     // Handle edge case: no categories available
-    if available.is_empty() {
-        let result = TurnAnalysisJs {
+    if analysis.available.is_empty() {
+        return TurnAnalysisJs {
             action: "score",
             recommended_category: None,
             category_score: None,
@@ -220,14 +391,8 @@ pub fn analyze_turn(
             expected_value: 0.0,
             categories: Vec::new(),
         };
-        return serde_wasm_bindgen::to_value(&result)
-            .map_err(|e| JsValue::from_str(&e.to_string()));
     }
 
-    // Run solver
-    let solver = TurnSolver::new();
-    let analysis = solver.analyze(&state, &available);
-
     // Convert to JS-friendly format
     let categories: Vec<CategoryAnalysisJs> = analysis
         .category_values
@@ -237,10 +402,11 @@ pub fn analyze_turn(
             immediate_score: cv.immediate_score,
             is_valid: cv.is_valid,
             expected_value: cv.expected_value,
+            is_optimal: cv.is_optimal,
         })
         .collect();
 
-    let result = match analysis.recommendation {
+    match analysis.recommendation {
         core::turn::Action::Score { category } => {
             // Find the immediate score for this category
             let score = analysis
@@ -274,9 +440,7 @@ pub fn analyze_turn(
                 categories,
             }
         }
-    };
-
-    serde_wasm_bindgen::to_value(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
 }
 
 /// Generate a human-readable explanation for a keep pattern.
@@ -301,6 +465,71 @@ fn generate_keep_explanation(counts: &[u8; 6]) -> String {
     }
 }
 
+// =============================================================================
+// TESTS
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dice_str_comma_delimited() {
+        assert_eq!(parse_dice_str("3,3,3,5,5"), Ok([3, 3, 3, 5, 5]));
+        assert_eq!(parse_dice_str("1, 2, 3, 4, 6"), Ok([1, 2, 3, 4, 6]));
+    }
+
+    #[test]
+    fn test_parse_dice_str_space_delimited() {
+        assert_eq!(parse_dice_str("6 5 4 3 2"), Ok([6, 5, 4, 3, 2]));
+        assert_eq!(parse_dice_str("  1\t1  1 1 1 "), Ok([1, 1, 1, 1, 1]));
+    }
+
+    #[test]
+    fn test_parse_dice_str_wrong_count() {
+        assert_eq!(
+            parse_dice_str("1,2,3,4"),
+            Err(DiceeError::InvalidDiceCount(4))
+        );
+        assert_eq!(
+            parse_dice_str("1 2 3 4 5 6"),
+            Err(DiceeError::InvalidDiceCount(6))
+        );
+        assert_eq!(parse_dice_str(""), Err(DiceeError::InvalidDiceCount(0)));
+    }
+
+    #[test]
+    fn test_parse_dice_str_out_of_range() {
+        assert_eq!(
+            parse_dice_str("1,2,7,4,5"),
+            Err(DiceeError::InvalidDieValue {
+                value: 7,
+                position: 2
+            })
+        );
+        assert_eq!(
+            parse_dice_str("0 2 3 4 5"),
+            Err(DiceeError::InvalidDieValue {
+                value: 0,
+                position: 0
+            })
+        );
+        assert!(matches!(
+            parse_dice_str("1,2,3,4,x"),
+            Err(DiceeError::UnparseableDie { position: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_positions() {
+        assert_eq!(validate_positions(&[1, 2, 3, 4, 6]), Ok(()));
+        assert_eq!(
+            validate_positions(&[0, 2, 3, 9, 6]),
+            Err(vec![(0, 0), (3, 9)])
+        );
+    }
+}
+
 // =============================================================================
 // TESTS FOR NEW WASM EXPORTS
 // =============================================================================
@@ -309,6 +538,33 @@ fn generate_keep_explanation(counts: &[u8; 6]) -> String {
 mod wasm_tests {
     use super::*;
 
+    #[test]
+    fn test_category_infos() {
+        let infos = category_infos();
+        assert_eq!(infos.len(), 13);
+
+        let dicee = &infos[Category::Dicee as usize];
+        assert_eq!(dicee.name, "Dicee");
+        assert_eq!(dicee.max_score, 50);
+        assert!((dicee.base_probability - 6.0 / 7776.0).abs() < 1e-12);
+
+        let chance = &infos[Category::Chance as usize];
+        assert_eq!(chance.max_score, 30);
+        assert!((chance.base_probability - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fresh_roll_entries() {
+        let entries = fresh_roll_entries();
+        assert_eq!(entries.len(), 252);
+
+        let total: f64 = entries.iter().map(|e| e.probability).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+
+        assert_eq!(entries[0].dice, [6, 6, 6, 6, 6]);
+        assert!((entries[0].probability - 1.0 / 7776.0).abs() < 1e-15);
+    }
+
     #[test]
     fn test_keep_explanation_all_same() {
         let counts = [0, 0, 5, 0, 0, 0]; // Five 3s
@@ -393,6 +649,21 @@ mod wasm_tests {
         assert!(analysis.category_values.is_empty());
     }
 
+    /// Test that a locked mask maps positions to kept face counts.
+    #[test]
+    fn test_locked_keep_from_mask() {
+        let dice = [3, 1, 3, 3, 3];
+        // Lock positions 0, 2, 3, 4 (the four 3s)
+        let locked = locked_keep_from_mask(&dice, 0b11101).unwrap();
+        assert_eq!(locked.counts(), &[0, 0, 4, 0, 0, 0]);
+
+        assert_eq!(
+            locked_keep_from_mask(&dice, 0).unwrap(),
+            KeepPattern::KEEP_NONE
+        );
+        assert!(locked_keep_from_mask(&dice, 0b100000).is_err());
+    }
+
     /// Test bitmask conversion for all categories.
     #[test]
     fn test_category_bitmask_full() {
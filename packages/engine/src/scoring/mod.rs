@@ -32,7 +32,11 @@ pub mod rules;
 
 // Re-export backward-compatible API (uses types::Category)
 // The solver imports directly from crate::scoring::rules::score
-pub use rules::{max_score, score_all_config, score_config, upper_target, ScoreResult};
+pub use rules::{
+    dominates, is_on_bonus_pace, max_score, score_all_config, score_breakdown, score_config,
+    upper_progress, upper_target, valid_categories, JokerRule, RuleFlags, ScoreBreakdown,
+    ScoreResult, ScoringRules, UPPER_BONUS_THRESHOLD, UPPER_BONUS_VALUE,
+};
 
 use crate::core::DiceConfig;
 use crate::types::{Category, Dice, ScoringResult};
@@ -105,6 +109,51 @@ pub fn score_all(dice: &Dice) -> Vec<ScoringResult> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Category as CoreCategory;
+
+    /// Cross-checks the three scoring paths on every configuration and category:
+    /// the solver's [`rules::score`], [`score_config`] and the dice-array [`score`].
+    ///
+    /// Categories are matched by index, which must also agree on the serialized
+    /// name. Scores must match exactly across the u8 and u16 widths. Validity
+    /// must match too, except that the solver treats an upper category as
+    /// always valid while the WASM API requires the face to be present.
+    fn verify_scoring_consistency() {
+        for &category in Category::all() {
+            let core = CoreCategory::from_index(category as usize).unwrap();
+            assert_eq!(
+                serde_json::to_string(&core).unwrap(),
+                serde_json::to_string(&category).unwrap()
+            );
+
+            for config in DiceConfig::iter_all() {
+                let solver = rules::score(&config, core);
+                let by_config = score_config(&config, category);
+                let by_dice = score(&config.to_dice(), category);
+
+                assert_eq!(by_config.category, category);
+                assert_eq!(by_dice.category, category);
+                assert_eq!(by_config.score, by_dice.score, "{config:?} {category:?}");
+                assert_eq!(by_config.valid, by_dice.valid, "{config:?} {category:?}");
+                assert_eq!(
+                    u16::from(solver.score),
+                    by_config.score,
+                    "{config:?} {category:?}"
+                );
+
+                let expected_valid = match core.upper_face() {
+                    Some(face) => config.count(face) > 0,
+                    None => solver.valid,
+                };
+                assert_eq!(by_config.valid, expected_valid, "{config:?} {category:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_scoring_paths_agree() {
+        verify_scoring_consistency();
+    }
 
     /// These tests verify that the new implementation produces identical results
     /// to the original MVP scoring.rs implementation.
@@ -15,10 +15,20 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::core::category::Category as CoreCategory;
+use crate::core::category::{Category as CoreCategory, CategorySet};
 use crate::core::config::DiceConfig;
 use crate::types::{Category as TypesCategory, ScoringResult};
 
+// =============================================================================
+// UPPER SECTION BONUS
+// =============================================================================
+
+/// Upper section total needed to earn the upper bonus.
+pub const UPPER_BONUS_THRESHOLD: u8 = 63;
+
+/// Points awarded when the upper section total reaches the threshold.
+pub const UPPER_BONUS_VALUE: u8 = 35;
+
 // =============================================================================
 // SCORE RESULT (for solver)
 // =============================================================================
@@ -26,7 +36,8 @@ use crate::types::{Category as TypesCategory, ScoringResult};
 /// Result of scoring a category: score and whether the category is "valid".
 ///
 /// A category is "valid" if the dice meet its requirements (e.g., Full House
-/// requires 3+2 pattern). Invalid categories score 0.
+/// requires 3+2 pattern). Invalid categories score 0. Upper categories and
+/// Chance have no requirements and are always valid, possibly with a 0 score.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScoreResult {
     /// The score for this category (0 if invalid).
@@ -128,22 +139,338 @@ pub fn score_all(config: &DiceConfig) -> [(CoreCategory, ScoreResult); 13] {
     CoreCategory::ALL.map(|cat| (cat, score(config, cat)))
 }
 
+/// Returns the categories `config` satisfies.
+///
+/// Upper categories count only when their face is present; lower
+/// categories count when their pattern is met, and Chance always does.
+pub fn valid_categories(config: &DiceConfig) -> CategorySet {
+    CoreCategory::ALL
+        .into_iter()
+        .filter(|&cat| match cat.upper_face() {
+            Some(face) => config.count(face) > 0,
+            None => score(config, cat).valid,
+        })
+        .collect()
+}
+
+/// Returns true if `config` weakly dominates `other` for the given categories.
+///
+/// Weak dominance means scoring at least as much as `other` in every available
+/// category. This is a partial order: it is reflexive and transitive, but many
+/// pairs are incomparable (neither dominates the other). An empty category set
+/// makes every pair mutually dominating.
+pub fn dominates(config: &DiceConfig, other: &DiceConfig, available: &CategorySet) -> bool {
+    available
+        .iter()
+        .all(|cat| score(config, cat).score >= score(other, cat).score)
+}
+
+// =============================================================================
+// SCORE BREAKDOWN
+// =============================================================================
+
+/// A category score together with the dice that produced it.
+///
+/// `Display` shows summed scores with their terms, e.g. `19 (3+3+3+5+5)`, and
+/// fixed scores such as Full House as just the total.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// The category scored.
+    pub category: CoreCategory,
+    /// The score for this category (0 if invalid).
+    pub total: u8,
+    /// The contributing faces in ascending order; empty when invalid.
+    ///
+    /// Upper categories list only the matching dice, n-of-a-kind and Chance
+    /// list all five, and fixed-score categories list the dice forming the pattern.
+    pub faces: Vec<u8>,
+}
+
+impl ScoreBreakdown {
+    /// Returns true if the total is the sum of the contributing faces.
+    pub fn is_sum(&self) -> bool {
+        !self.faces.is_empty()
+            && self.faces.iter().map(|&f| u16::from(f)).sum::<u16>() == u16::from(self.total)
+    }
+}
+
+impl std::fmt::Display for ScoreBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.total)?;
+        if self.is_sum() {
+            let terms: Vec<String> = self.faces.iter().map(u8::to_string).collect();
+            write!(f, " ({})", terms.join("+"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Scores a category and reports which dice contribute to the score.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::core::{DiceConfig, Category};
+/// use dicee_engine::scoring::rules::score_breakdown;
+///
+/// let config = DiceConfig::from_dice(&[3, 3, 3, 5, 5]);
+/// let breakdown = score_breakdown(&config, Category::ThreeOfAKind);
+/// assert_eq!(breakdown.to_string(), "19 (3+3+3+5+5)");
+///
+/// let breakdown = score_breakdown(&config, Category::Fives);
+/// assert_eq!(breakdown.faces, vec![5, 5]);
+/// ```
+pub fn score_breakdown(config: &DiceConfig, category: CoreCategory) -> ScoreBreakdown {
+    let result = score(config, category);
+    let faces = if !result.valid {
+        Vec::new()
+    } else if let Some(face) = category.upper_face() {
+        vec![face; usize::from(config.count(face))]
+    } else if category == CoreCategory::SmallStraight {
+        let start = (1..=3)
+            .find(|&low| (low..low + 4).all(|face| config.count(face) > 0))
+            .unwrap_or(1);
+        (start..start + 4).collect()
+    } else {
+        config.to_dice().to_vec()
+    };
+    ScoreBreakdown {
+        category,
+        total: result.score,
+        faces,
+    }
+}
+
+// =============================================================================
+// JOKER RULE
+// =============================================================================
+
+/// How a Dicee is scored when rolled after the Dicee box has been filled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum JokerRule {
+    /// No joker: the dice score normally in any open category.
+    #[default]
+    Disabled,
+    /// Any open category may be used, and Full House and the straights
+    /// score their fixed values.
+    FreeChoice,
+    /// The matching upper box must be used if open. Otherwise any open lower
+    /// category scores as a joker, and only if the lower section is full may
+    /// an open upper box be zeroed.
+    ForcedUpper,
+}
+
+/// Scores a configuration in a category under a joker rule.
+///
+/// The joker applies when the dice are a Dicee and the Dicee box is not in
+/// `open`. Returns `None` if `category` is not open or the rule forbids it.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::core::{Category, CategorySet, DiceConfig};
+/// use dicee_engine::scoring::rules::{score_joker, JokerRule};
+///
+/// let config = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+/// let open = CategorySet::new().with(Category::Fours).with(Category::FullHouse);
+///
+/// // Fours is open, so it must be used
+/// assert_eq!(score_joker(&config, Category::Fours, &open, JokerRule::ForcedUpper).unwrap().score, 20);
+/// assert!(score_joker(&config, Category::FullHouse, &open, JokerRule::ForcedUpper).is_none());
+/// ```
+pub fn score_joker(
+    config: &DiceConfig,
+    category: CoreCategory,
+    open: &CategorySet,
+    rule: JokerRule,
+) -> Option<ScoreResult> {
+    if !open.contains(category) {
+        return None;
+    }
+
+    let joker_applies = config.is_dicee() && !open.contains(CoreCategory::Dicee);
+    if !joker_applies {
+        return Some(score(config, category));
+    }
+
+    match rule {
+        JokerRule::Disabled => Some(score(config, category)),
+        JokerRule::FreeChoice => Some(joker_score(config, category)),
+        JokerRule::ForcedUpper => {
+            let matching_upper = CoreCategory::UPPER[(config.mode_face() - 1) as usize];
+            let lower_open = open.iter().any(CoreCategory::is_lower);
+
+            let allowed = if open.contains(matching_upper) {
+                category == matching_upper
+            } else if lower_open {
+                category.is_lower()
+            } else {
+                true
+            };
+
+            allowed.then(|| joker_score(config, category))
+        }
+    }
+}
+
+/// Returns the open categories a configuration may be entered in under a joker rule.
+pub fn joker_choices(config: &DiceConfig, open: &CategorySet, rule: JokerRule) -> CategorySet {
+    open.iter()
+        .filter(|&cat| score_joker(config, cat, open, rule).is_some())
+        .collect()
+}
+
+/// Scores a Dicee used as a joker: fixed-score lower categories count as satisfied.
+fn joker_score(config: &DiceConfig, category: CoreCategory) -> ScoreResult {
+    match category {
+        CoreCategory::FullHouse | CoreCategory::SmallStraight | CoreCategory::LargeStraight => {
+            ScoreResult::valid(category.fixed_score().unwrap_or(0))
+        }
+        _ => score(config, category),
+    }
+}
+
+// =============================================================================
+// HOUSE RULES
+// =============================================================================
+
+/// A set of house-rule toggles, stored as a bitset.
+///
+/// Flags combine with `|`. The default is [`RuleFlags::NONE`], which is the
+/// standard game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RuleFlags(u8);
+
+impl RuleFlags {
+    /// Standard rules: any open category may be zeroed.
+    pub const NONE: Self = Self(0);
+
+    /// Upper boxes may not be entered with a zero score.
+    pub const NO_ZERO_UPPER: Self = Self(1 << 0);
+
+    /// Lower boxes other than Dicee may not be scratched with a zero score.
+    pub const NO_ZERO_LOWER: Self = Self(1 << 1);
+
+    /// The Dicee box may not be scratched with a zero score.
+    pub const NO_ZERO_DICEE: Self = Self(1 << 2);
+
+    /// Creates flags from raw bits, ignoring unknown bits.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & 0b111)
+    }
+
+    /// Returns the raw bits.
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if no flags are set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if every flag in `other` is set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns true if these flags forbid entering a zero in `category`.
+    #[inline]
+    pub const fn forbids_zero(self, category: CoreCategory) -> bool {
+        let flag = match category {
+            CoreCategory::Dicee => Self::NO_ZERO_DICEE,
+            _ if category.is_upper() => Self::NO_ZERO_UPPER,
+            _ => Self::NO_ZERO_LOWER,
+        };
+        self.contains(flag)
+    }
+}
+
+impl std::ops::BitOr for RuleFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// Scoring under a set of house rules.
+///
+/// Rules only restrict where a roll may be entered; the score of a legal
+/// entry is unchanged. If the rules would leave no legal category, every open
+/// category becomes legal, since a turn must always end with a score.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::core::{Category, CategorySet, DiceConfig};
+/// use dicee_engine::scoring::rules::{RuleFlags, ScoringRules};
+///
+/// let rules = ScoringRules::new(RuleFlags::NO_ZERO_LOWER);
+/// let config = DiceConfig::from_dice(&[2, 3, 4, 6, 6]);
+/// let open = CategorySet::new().with(Category::Ones).with(Category::FullHouse);
+///
+/// // Ones may still be zeroed, Full House may not
+/// assert_eq!(rules.legal_categories(&config, &open), CategorySet::new().with(Category::Ones));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ScoringRules {
+    /// The house rules in effect.
+    pub flags: RuleFlags,
+}
+
+impl ScoringRules {
+    /// Standard rules with no house-rule restrictions.
+    pub const STANDARD: Self = Self::new(RuleFlags::NONE);
+
+    /// Creates scoring rules from house-rule flags.
+    #[inline]
+    pub const fn new(flags: RuleFlags) -> Self {
+        Self { flags }
+    }
+
+    /// Returns true if `config` may be entered in `category` under these
+    /// rules, ignoring the no-legal-category fallback.
+    #[inline]
+    pub fn is_legal(&self, config: &DiceConfig, category: CoreCategory) -> bool {
+        !self.flags.forbids_zero(category) || score(config, category).score > 0
+    }
+
+    /// Returns the open categories `config` may be entered in.
+    ///
+    /// Falls back to all of `open` when the rules forbid every open category.
+    pub fn legal_categories(&self, config: &DiceConfig, open: &CategorySet) -> CategorySet {
+        if self.flags.is_empty() {
+            return *open;
+        }
+
+        let legal: CategorySet = open
+            .iter()
+            .filter(|&cat| self.is_legal(config, cat))
+            .collect();
+        if legal.is_empty() {
+            *open
+        } else {
+            legal
+        }
+    }
+}
+
 // =============================================================================
 // Helper functions for u8 scoring (used by solver API)
 // =============================================================================
 
+/// Scores an upper category: the sum of dice showing `face`.
+///
+/// Upper categories have no pattern requirement, so the result is always
+/// valid — "valid" means legal to score, not nonzero — even when it is 0.
 #[inline]
 fn score_upper_u8(config: &DiceConfig, face: u8) -> ScoreResult {
-    let count = config.count(face);
-    let s = face * count;
-    if count > 0 {
-        ScoreResult::valid(s)
-    } else {
-        ScoreResult {
-            score: 0,
-            valid: true,
-        } // Upper section is always "valid" but may score 0
-    }
+    ScoreResult::valid(config.face_sum(face))
 }
 
 #[inline]
@@ -362,6 +689,26 @@ pub const fn upper_target(category: TypesCategory) -> u16 {
     }
 }
 
+/// Returns an upper category's score and its deviation from bonus pace.
+///
+/// Pace is 3 × face per upper category, which sums to the 63-point bonus
+/// threshold. A positive deviation means the score is ahead of pace.
+/// Returns `None` for lower section categories.
+pub fn upper_progress(config: &DiceConfig, category: CoreCategory) -> Option<(u16, i16)> {
+    let face = category.upper_face()?;
+    let par = category.bonus_par()?;
+    let score = config.face_sum(face);
+    let deviation = i16::from(score) - i16::from(par);
+    Some((u16::from(score), deviation))
+}
+
+/// Returns true if scoring `config` in an upper category meets its bonus par.
+///
+/// See [`CoreCategory::bonus_par`]. Always false for lower section categories.
+pub fn is_on_bonus_pace(config: &DiceConfig, category: CoreCategory) -> bool {
+    upper_progress(config, category).is_some_and(|(_, deviation)| deviation >= 0)
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -500,6 +847,39 @@ mod tests {
         assert_eq!(max_score(TypesCategory::Sixes), 30);
     }
 
+    #[test]
+    fn test_upper_always_valid() {
+        let no_ones = DiceConfig::from_dice(&[2, 3, 4, 5, 6]);
+        assert_eq!(
+            score(&no_ones, CoreCategory::Ones),
+            ScoreResult {
+                score: 0,
+                valid: true
+            }
+        );
+        assert_eq!(score(&no_ones, CoreCategory::Sixes), ScoreResult::valid(6));
+    }
+
+    #[test]
+    fn test_upper_progress() {
+        let three_fours = DiceConfig::from_dice(&[4, 4, 4, 1, 2]);
+        assert_eq!(
+            upper_progress(&three_fours, CoreCategory::Fours),
+            Some((12, 0))
+        );
+
+        let four_fours = DiceConfig::from_dice(&[4, 4, 4, 4, 2]);
+        assert_eq!(
+            upper_progress(&four_fours, CoreCategory::Fours),
+            Some((16, 4))
+        );
+        assert_eq!(
+            upper_progress(&four_fours, CoreCategory::Sixes),
+            Some((0, -18))
+        );
+        assert_eq!(upper_progress(&four_fours, CoreCategory::Chance), None);
+    }
+
     #[test]
     fn test_upper_targets() {
         assert_eq!(upper_target(TypesCategory::Ones), 3);
@@ -525,6 +905,93 @@ mod tests {
         assert_eq!(score(&config, CoreCategory::Dicee).score, 0);
     }
 
+    #[test]
+    fn test_joker_forced_upper() {
+        let config = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+        let open = CategorySet::new()
+            .with(CoreCategory::Fours)
+            .with(CoreCategory::FullHouse)
+            .with(CoreCategory::Chance);
+
+        // Matching upper box open: it is the only choice
+        let rule = JokerRule::ForcedUpper;
+        assert_eq!(
+            score_joker(&config, CoreCategory::Fours, &open, rule),
+            Some(ScoreResult::valid(20))
+        );
+        assert_eq!(
+            score_joker(&config, CoreCategory::FullHouse, &open, rule),
+            None
+        );
+        assert_eq!(
+            score_joker(&config, CoreCategory::Chance, &open, rule),
+            None
+        );
+        assert_eq!(
+            joker_choices(&config, &open, rule),
+            CategorySet::new().with(CoreCategory::Fours)
+        );
+
+        // With the Dicee box open, the joker doesn't apply
+        let dicee_open = open.with(CoreCategory::Dicee);
+        assert_eq!(joker_choices(&config, &dicee_open, rule), dicee_open);
+    }
+
+    #[test]
+    fn test_joker_fall_through() {
+        let config = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+        let rule = JokerRule::ForcedUpper;
+
+        // Fours filled: lower categories score as jokers, other upper boxes are closed
+        let open = CategorySet::new()
+            .with(CoreCategory::Twos)
+            .with(CoreCategory::FullHouse)
+            .with(CoreCategory::LargeStraight)
+            .with(CoreCategory::Chance);
+        assert_eq!(
+            score_joker(&config, CoreCategory::FullHouse, &open, rule),
+            Some(ScoreResult::valid(25))
+        );
+        assert_eq!(
+            score_joker(&config, CoreCategory::LargeStraight, &open, rule),
+            Some(ScoreResult::valid(40))
+        );
+        assert_eq!(
+            score_joker(&config, CoreCategory::Chance, &open, rule),
+            Some(ScoreResult::valid(20))
+        );
+        assert_eq!(score_joker(&config, CoreCategory::Twos, &open, rule), None);
+
+        // Lower section full: an open upper box must take a zero
+        let upper_only = CategorySet::new().with(CoreCategory::Twos);
+        assert_eq!(
+            score_joker(&config, CoreCategory::Twos, &upper_only, rule),
+            Some(ScoreResult {
+                score: 0,
+                valid: true
+            })
+        );
+    }
+
+    #[test]
+    fn test_joker_disabled_scores_normally() {
+        let config = DiceConfig::from_dice(&[4, 4, 4, 4, 4]);
+        let open = CategorySet::new().with(CoreCategory::FullHouse);
+        assert_eq!(
+            score_joker(&config, CoreCategory::FullHouse, &open, JokerRule::Disabled),
+            Some(ScoreResult::invalid())
+        );
+        assert_eq!(
+            score_joker(
+                &config,
+                CoreCategory::FullHouse,
+                &open,
+                JokerRule::FreeChoice
+            ),
+            Some(ScoreResult::valid(25))
+        );
+    }
+
     #[test]
     fn test_solver_api_score_all() {
         let config = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
@@ -540,4 +1007,120 @@ mod tests {
         assert!(ls.1.valid);
         assert_eq!(ls.1.score, 40);
     }
+
+    #[test]
+    fn test_rule_flags() {
+        let flags = RuleFlags::NO_ZERO_UPPER | RuleFlags::NO_ZERO_DICEE;
+        assert!(flags.forbids_zero(CoreCategory::Sixes));
+        assert!(flags.forbids_zero(CoreCategory::Dicee));
+        assert!(!flags.forbids_zero(CoreCategory::FullHouse));
+        assert_eq!(RuleFlags::from_bits(flags.bits()), flags);
+        assert!(RuleFlags::default().is_empty());
+    }
+
+    #[test]
+    fn test_scoring_rules_fallback() {
+        let rules = ScoringRules::new(RuleFlags::NO_ZERO_LOWER | RuleFlags::NO_ZERO_DICEE);
+        let config = DiceConfig::from_dice(&[1, 2, 3, 5, 6]);
+        let open = CategorySet::new()
+            .with(CoreCategory::FullHouse)
+            .with(CoreCategory::Dicee);
+
+        // Every open box is forbidden, so all of them become legal
+        assert!(!rules.is_legal(&config, CoreCategory::FullHouse));
+        assert_eq!(rules.legal_categories(&config, &open), open);
+    }
+
+    #[test]
+    fn test_score_breakdown() {
+        let config = DiceConfig::from_dice(&[3, 3, 3, 5, 5]);
+
+        let tok = score_breakdown(&config, CoreCategory::ThreeOfAKind);
+        assert_eq!(tok.total, 19);
+        assert_eq!(tok.faces, vec![3, 3, 3, 5, 5]);
+        assert_eq!(tok.to_string(), "19 (3+3+3+5+5)");
+
+        let threes = score_breakdown(&config, CoreCategory::Threes);
+        assert_eq!(threes.total, 9);
+        assert_eq!(threes.faces, vec![3, 3, 3]);
+
+        let full_house = score_breakdown(&config, CoreCategory::FullHouse);
+        assert_eq!(full_house.to_string(), "25");
+
+        let four = score_breakdown(&config, CoreCategory::FourOfAKind);
+        assert_eq!(four.total, 0);
+        assert_eq!(four.faces, Vec::<u8>::new());
+
+        let straight = DiceConfig::from_dice(&[2, 3, 4, 5, 5]);
+        assert_eq!(
+            score_breakdown(&straight, CoreCategory::SmallStraight).faces,
+            vec![2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn test_is_on_bonus_pace() {
+        let four_fours = DiceConfig::from_dice(&[4, 4, 4, 4, 2]);
+        let two_fours = DiceConfig::from_dice(&[4, 4, 1, 2, 3]);
+        let three_fours = DiceConfig::from_dice(&[4, 4, 4, 1, 2]);
+
+        assert!(is_on_bonus_pace(&four_fours, CoreCategory::Fours));
+        assert!(is_on_bonus_pace(&three_fours, CoreCategory::Fours));
+        assert!(!is_on_bonus_pace(&two_fours, CoreCategory::Fours));
+        assert!(!is_on_bonus_pace(&four_fours, CoreCategory::Chance));
+    }
+
+    #[test]
+    fn test_dominates() {
+        let sixes = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+        let fives = DiceConfig::from_dice(&[5, 5, 5, 5, 5]);
+
+        let sixes_and_chance = CategorySet::new()
+            .with(CoreCategory::Sixes)
+            .with(CoreCategory::Chance);
+        assert!(dominates(&sixes, &fives, &sixes_and_chance));
+        assert!(!dominates(&fives, &sixes, &sixes_and_chance));
+
+        // Fives scores 25 for [5,5,5,5,5] and 0 for [6,6,6,6,6]
+        let upper_and_chance = CategorySet::upper_only().with(CoreCategory::Chance);
+        assert!(!dominates(&sixes, &fives, &upper_and_chance));
+        assert!(!dominates(&fives, &sixes, &upper_and_chance));
+
+        // Reflexive
+        assert!(dominates(&sixes, &sixes, &CategorySet::all()));
+    }
+
+    #[test]
+    fn test_dominates_incomparable() {
+        let straight = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
+        let sixes = DiceConfig::from_dice(&[6, 6, 6, 6, 6]);
+        let all = CategorySet::all();
+
+        assert!(!dominates(&straight, &sixes, &all));
+        assert!(!dominates(&sixes, &straight, &all));
+    }
+
+    #[test]
+    fn test_valid_categories() {
+        let full_house = DiceConfig::from_dice(&[2, 2, 6, 6, 6]);
+        let expected: CategorySet = [
+            CoreCategory::Twos,
+            CoreCategory::Sixes,
+            CoreCategory::ThreeOfAKind,
+            CoreCategory::FullHouse,
+            CoreCategory::Chance,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(valid_categories(&full_house), expected);
+
+        let straight = DiceConfig::from_dice(&[1, 2, 3, 4, 5]);
+        let valid = valid_categories(&straight);
+        assert!(
+            valid.contains(CoreCategory::SmallStraight)
+                && valid.contains(CoreCategory::LargeStraight)
+        );
+        assert!(!valid.contains(CoreCategory::Sixes));
+        assert_eq!(valid.len(), 8);
+    }
 }
@@ -13,4 +13,7 @@ pub mod probability;
 pub mod table;
 
 pub use probability::Probability;
-pub use table::{TransitionEntry, TransitionTable, TRANSITION_TABLE};
+pub use table::{
+    keep_evs, LazyTransitionTable, TransitionEntry, TransitionSource, TransitionTable, Transitions,
+    TRANSITION_TABLE,
+};
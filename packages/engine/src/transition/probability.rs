@@ -37,6 +37,29 @@ impl Probability {
         }
     }
 
+    /// Creates a probability, clamping out-of-range values into [0, 1].
+    ///
+    /// For display and import paths where rounding may push a value slightly
+    /// outside the range. NaN becomes zero. Use [`new`](Self::new) to reject
+    /// bad values instead.
+    pub fn from_f64_clamped(value: f64) -> Self {
+        if value.is_nan() {
+            Self::ZERO
+        } else {
+            Self(value.clamp(0.0, 1.0))
+        }
+    }
+
+    /// Creates a probability from a count of favorable outcomes out of a total.
+    ///
+    /// Returns an error if `total` is zero or `favorable` exceeds `total`.
+    pub fn from_ratio(favorable: u32, total: u32) -> Result<Self> {
+        if total == 0 || favorable > total {
+            return Err(DiceeError::InvalidRatio { favorable, total });
+        }
+        Ok(Self(favorable as f64 / total as f64))
+    }
+
     /// Creates a probability without validation.
     ///
     /// # Safety
@@ -64,6 +87,26 @@ impl Probability {
     pub fn is_one(self) -> bool {
         (self.0 - 1.0).abs() < f64::EPSILON
     }
+
+    /// Returns true if the two probabilities differ by at most `tol`.
+    ///
+    /// The bound is inclusive, so a difference of exactly `tol` compares equal.
+    /// A negative or NaN tolerance never compares equal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dicee_engine::transition::Probability;
+    ///
+    /// let sixth = Probability::from_ratio(1, 6).unwrap();
+    /// let approx = Probability::new(0.1667).unwrap();
+    /// assert!(sixth.approx_eq(approx, 1e-4));
+    /// assert!(!sixth.approx_eq(approx, 1e-6));
+    /// ```
+    #[inline]
+    pub fn approx_eq(self, other: Probability, tol: f64) -> bool {
+        (self.0 - other.0).abs() <= tol
+    }
 }
 
 impl fmt::Debug for Probability {
@@ -132,10 +175,36 @@ pub const fn factorial(n: u8) -> u64 {
     FACTORIALS[n as usize]
 }
 
-/// Computes the multinomial coefficient: n! / (k₁! × k₂! × ... × kₘ!).
+/// Computes the multinomial coefficient n! / (k₁! × k₂! × ... × k₆!) for any counts.
+///
+/// Built as a product of binomial coefficients, so no factorial larger than
+/// the result is needed. Returns `None` if the computation overflows `u64`.
+/// For at most 5 dice prefer [`multinomial_coefficient_dice`].
+pub fn multinomial_coefficient(counts: &[u8; 6]) -> Option<u64> {
+    let mut result = 1u64;
+    let mut total = 0u64;
+    for &count in counts {
+        // Multiply by C(total + count, count), one factor at a time; each
+        // intermediate quotient is itself a binomial coefficient, so exact
+        let mut binomial = 1u64;
+        for i in 1..=u64::from(count) {
+            binomial = binomial.checked_mul(total + i)? / i;
+        }
+        result = result.checked_mul(binomial)?;
+        total += u64::from(count);
+    }
+    Some(result)
+}
+
+/// Computes the multinomial coefficient for a dice roll: 5! / (c₁! × c₂! × ... × c₆!).
+///
+/// Uses the factorial table, so the counts must sum to at most 10.
 ///
-/// For dice: 5! / (c₁! × c₂! × ... × c₆!)
-pub fn multinomial_coefficient(counts: &[u8; 6]) -> u32 {
+/// # Panics
+///
+/// Panics if the counts sum to more than 10.
+#[inline]
+pub fn multinomial_coefficient_dice(counts: &[u8; 6]) -> u32 {
     let n: u8 = counts.iter().sum();
     let numerator = factorial(n);
     let denominator: u64 = counts.iter().map(|&c| factorial(c)).product();
@@ -201,7 +270,7 @@ pub fn roll_outcome_probability(rolled_counts: &[u8; 6], dice_rolled: u8) -> Pro
         };
     }
 
-    let coefficient = multinomial_coefficient(rolled_counts);
+    let coefficient = multinomial_coefficient_dice(rolled_counts);
     let base_prob = inv_power_of_six(dice_rolled);
     let prob = (coefficient as f64) * base_prob;
 
@@ -209,6 +278,31 @@ pub fn roll_outcome_probability(rolled_counts: &[u8; 6], dice_rolled: u8) -> Pro
     unsafe { Probability::new_unchecked(prob) }
 }
 
+/// Computes the probability of rolling a specific outcome, validating the input.
+///
+/// Like [`roll_outcome_probability`], but for inputs that are not known to be
+/// consistent: returns an error if `dice_rolled` exceeds 5 or the counts do
+/// not sum to it.
+///
+/// # Example
+///
+/// ```rust
+/// use dicee_engine::transition::probability::try_roll_outcome_probability;
+///
+/// assert!(try_roll_outcome_probability(&[1, 0, 0, 0, 0, 0], 1).is_ok());
+/// assert!(try_roll_outcome_probability(&[2, 0, 0, 0, 0, 0], 1).is_err());
+/// ```
+pub fn try_roll_outcome_probability(
+    rolled_counts: &[u8; 6],
+    dice_rolled: u8,
+) -> Result<Probability> {
+    let sum: u32 = rolled_counts.iter().map(|&c| u32::from(c)).sum();
+    if dice_rolled > 5 || sum != u32::from(dice_rolled) {
+        return Err(DiceeError::InvalidRollCounts { sum, dice_rolled });
+    }
+    Ok(roll_outcome_probability(rolled_counts, dice_rolled))
+}
+
 /// Computes the probability of transitioning from a partial state to a target config.
 ///
 /// Given kept dice counts and the number of dice to roll, compute the probability
@@ -301,7 +395,8 @@ where
 /// Iterates over all possible outcomes for rolling k dice.
 ///
 /// This is stars-and-bars enumeration: distribute k dice across 6 faces.
-fn for_each_roll_outcome<F>(dice_to_roll: u8, mut f: F)
+/// Outcomes are visited in lexicographic order of counts.
+pub(crate) fn for_each_roll_outcome<F>(dice_to_roll: u8, mut f: F)
 where
     F: FnMut(&[u8; 6]),
 {
@@ -361,8 +456,9 @@ pub mod exact {
         use super::factorial;
 
         let n = dice_rolled;
-        let numerator = factorial(n) as i64;
-        let denominator: i64 = counts.iter().map(|&c| factorial(c) as i64).product();
+        // Factorials of at most 5 dice are tiny, so the casts cannot wrap
+        let numerator = factorial(n).cast_signed();
+        let denominator: i64 = counts.iter().map(|&c| factorial(c).cast_signed()).product();
         let coeff = Ratio::new(numerator, denominator);
 
         // Multiply by (1/6)^n
@@ -380,6 +476,48 @@ pub mod exact {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_ratio() {
+        let p = Probability::from_ratio(1, 6).unwrap();
+        assert!(p.approx_eq(Probability::new(0.1667).unwrap(), 1e-4));
+
+        assert_eq!(Probability::from_ratio(0, 6).unwrap(), Probability::ZERO);
+        assert_eq!(Probability::from_ratio(6, 6).unwrap(), Probability::ONE);
+
+        assert!(matches!(
+            Probability::from_ratio(7, 6),
+            Err(DiceeError::InvalidRatio {
+                favorable: 7,
+                total: 6
+            })
+        ));
+        assert!(Probability::from_ratio(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_from_f64_clamped() {
+        assert_eq!(Probability::from_f64_clamped(1.000_000_1), Probability::ONE);
+        assert_eq!(Probability::from_f64_clamped(-0.0001), Probability::ZERO);
+        assert_eq!(Probability::from_f64_clamped(0.25).get(), 0.25);
+        assert_eq!(Probability::from_f64_clamped(f64::NAN), Probability::ZERO);
+        assert!(Probability::new(1.000_000_1).is_err());
+    }
+
+    #[test]
+    fn test_approx_eq_boundary() {
+        let half = Probability::new(0.5).unwrap();
+        let three_quarters = Probability::new(0.75).unwrap();
+
+        // Exactly representable difference of 0.25: inclusive at the boundary
+        assert!(half.approx_eq(three_quarters, 0.25));
+        assert!(three_quarters.approx_eq(half, 0.25));
+        assert!(!half.approx_eq(three_quarters, 0.25 - f64::EPSILON));
+
+        assert!(half.approx_eq(half, 0.0));
+        assert!(!half.approx_eq(half, -1.0));
+        assert!(!half.approx_eq(half, f64::NAN));
+    }
+
     #[test]
     fn test_factorial() {
         assert_eq!(factorial(0), 1);
@@ -390,13 +528,32 @@ mod tests {
     #[test]
     fn test_multinomial_coefficient() {
         // 5!/(5!) = 1 (all same)
-        assert_eq!(multinomial_coefficient(&[5, 0, 0, 0, 0, 0]), 1);
+        assert_eq!(multinomial_coefficient_dice(&[5, 0, 0, 0, 0, 0]), 1);
 
         // 5!/(1!×1!×1!×1!×1!) = 120 (all different)
-        assert_eq!(multinomial_coefficient(&[1, 1, 1, 1, 1, 0]), 120);
+        assert_eq!(multinomial_coefficient_dice(&[1, 1, 1, 1, 1, 0]), 120);
 
         // 5!/(2!×3!) = 10 (pair + three)
-        assert_eq!(multinomial_coefficient(&[2, 3, 0, 0, 0, 0]), 10);
+        assert_eq!(multinomial_coefficient_dice(&[2, 3, 0, 0, 0, 0]), 10);
+    }
+
+    #[test]
+    fn test_multinomial_coefficient_general() {
+        for dice_to_roll in 0..=5 {
+            for_each_roll_outcome(dice_to_roll, |counts| {
+                assert_eq!(
+                    multinomial_coefficient(counts),
+                    Some(u64::from(multinomial_coefficient_dice(counts)))
+                );
+            });
+        }
+
+        // 8!/(3!×3!×2!) = 560, past the 5-dice range
+        assert_eq!(multinomial_coefficient(&[3, 3, 2, 0, 0, 0]), Some(560));
+        // 12!/(2!^6) = 7484400, past the factorial table
+        assert_eq!(multinomial_coefficient(&[2; 6]), Some(7_484_400));
+        // C(510, 255) does not fit in a u64
+        assert_eq!(multinomial_coefficient(&[255, 255, 0, 0, 0, 0]), None);
     }
 
     #[test]
@@ -409,7 +566,7 @@ mod tests {
             });
 
             assert!(
-                (total.get() - 1.0).abs() < 1e-10,
+                total.approx_eq(Probability::ONE, 1e-10),
                 "Probabilities for {} dice sum to {}, expected 1.0",
                 dice_to_roll,
                 total.get()
@@ -428,6 +585,30 @@ mod tests {
         assert!((p.get() - 1.0 / 6.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_try_roll_outcome_probability() {
+        assert_eq!(
+            try_roll_outcome_probability(&[1, 1, 0, 0, 0, 0], 3),
+            Err(DiceeError::InvalidRollCounts {
+                sum: 2,
+                dice_rolled: 3
+            })
+        );
+        assert!(try_roll_outcome_probability(&[6, 0, 0, 0, 0, 0], 6).is_err());
+        assert!(try_roll_outcome_probability(&[255, 255, 0, 0, 0, 0], 5).is_err());
+
+        for counts in [[5, 0, 0, 0, 0, 0], [1, 1, 1, 1, 1, 0], [0, 0, 2, 0, 3, 0]] {
+            assert_eq!(
+                try_roll_outcome_probability(&counts, 5).unwrap(),
+                roll_outcome_probability(&counts, 5)
+            );
+        }
+        assert_eq!(
+            try_roll_outcome_probability(&[0; 6], 0).unwrap(),
+            Probability::ONE
+        );
+    }
+
     #[test]
     fn test_transition_probability() {
         // Keeping two 3s, rolling 3 dice, want three 3s and two 5s
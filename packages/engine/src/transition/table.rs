@@ -2,15 +2,19 @@
 //!
 //! This module provides a lazily-computed table of transition probabilities
 //! from each possible partial dice state to each target configuration.
+//! Only the roll distributions are stored; targets are derived from the kept dice.
 
-use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
 
 use serde::{Deserialize, Serialize};
 
-use super::probability::{roll_outcome_probability, Probability};
-use crate::core::config::{ConfigIndex, DiceConfig, ALL_CONFIGS};
-use crate::core::keep::PartialDice;
+use super::probability::{
+    for_each_roll_outcome, outcome_count, roll_outcome_probability, Probability,
+};
+use crate::core::config::{ConfigIndex, DiceConfig};
+use crate::core::error::DiceeError;
+use crate::core::keep::{KeepPattern, PartialDice};
+use crate::Result;
 
 // =============================================================================
 // TRANSITION ENTRY
@@ -37,175 +41,346 @@ impl TransitionEntry {
 }
 
 // =============================================================================
-// PARTIAL DICE KEY
+// ROLL OUTCOME
 // =============================================================================
 
-/// A hashable key for partial dice states.
+/// One outcome of rolling some number of dice: the rolled face counts and their probability.
+#[derive(Clone, Copy, Debug)]
+struct RollOutcome {
+    rolled: [u8; 6],
+    probability: Probability,
+}
+
+// =============================================================================
+// TRANSITIONS
+// =============================================================================
+
+/// Iterator over the transition distribution of one partial dice state.
 ///
-/// We key by (kept counts, dice to roll) since that fully determines
-/// the transition distribution.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct PartialKey {
+/// Yields a [`TransitionEntry`] per reachable configuration, in canonical
+/// configuration order. Returned by [`TransitionTable::get`].
+#[derive(Clone, Debug)]
+pub struct Transitions<'a> {
     kept: [u8; 6],
-    to_roll: u8,
+    outcomes: std::slice::Iter<'a, RollOutcome>,
 }
 
-impl From<&PartialDice> for PartialKey {
-    fn from(partial: &PartialDice) -> Self {
+impl<'a> Transitions<'a> {
+    fn new(kept: &[u8; 6], outcomes: &'a [RollOutcome]) -> Self {
         Self {
-            kept: *partial.kept_counts(),
-            to_roll: partial.dice_to_roll(),
+            kept: *kept,
+            outcomes: outcomes.iter(),
         }
     }
+
+    /// Yields each reachable configuration with its probability, without indexing it.
+    fn configs(self) -> impl Iterator<Item = (DiceConfig, Probability)> + 'a {
+        let kept = self.kept;
+        self.outcomes
+            .map(move |outcome| (combine(&kept, &outcome.rolled), outcome.probability))
+    }
+}
+
+impl Iterator for Transitions<'_> {
+    type Item = TransitionEntry;
+
+    #[inline]
+    fn next(&mut self) -> Option<TransitionEntry> {
+        let outcome = self.outcomes.next()?;
+        let target = combine(&self.kept, &outcome.rolled);
+        Some(TransitionEntry::new(target.to_index(), outcome.probability))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.outcomes.size_hint()
+    }
 }
 
+impl ExactSizeIterator for Transitions<'_> {}
+
 // =============================================================================
 // TRANSITION TABLE
 // =============================================================================
 
 /// Precomputed transition probabilities from partial dice states to configurations.
 ///
-/// For each valid (kept pattern, dice to roll) pair, stores the distribution
-/// over reachable target configurations.
+/// Holds one roll distribution per number of dice rolled (0..=5), 462 outcomes
+/// in all. A partial state's targets are its kept counts plus each rolled
+/// outcome, so they are derived on lookup rather than stored per state.
 #[derive(Debug)]
 pub struct TransitionTable {
-    /// Map from partial state to list of (target, probability) pairs.
-    /// Only stores non-zero probabilities.
-    transitions: HashMap<PartialKey, Vec<TransitionEntry>>,
+    /// Roll outcomes indexed by the number of dice rolled.
+    /// Outcomes are in lexicographic order of rolled counts, which keeps
+    /// offset targets in canonical configuration order.
+    rolls: [Vec<RollOutcome>; 6],
 }
 
 impl TransitionTable {
     /// Builds the complete transition table.
     ///
-    /// This is an expensive operation that enumerates all possible transitions.
+    /// Enumerates every roll outcome for 0 through 5 dice.
     pub fn build() -> Self {
-        let mut transitions: HashMap<PartialKey, Vec<TransitionEntry>> = HashMap::new();
-
-        // For each number of dice to roll (0..=5)
-        for to_roll in 0u8..=5 {
-            // Enumerate all possible keep patterns that result in rolling `to_roll` dice
-            for_each_keep_pattern(5 - to_roll, |kept| {
-                let key = PartialKey { kept, to_roll };
-
-                let mut entries = Vec::new();
-
-                // For each possible target configuration
-                for (idx, target) in ALL_CONFIGS.iter().enumerate() {
-                    // Compute probability of reaching this target from kept state
-                    if let Some(prob) = compute_transition_prob(&kept, target, to_roll) {
-                        if !prob.is_zero() {
-                            entries.push(TransitionEntry {
-                                // Safety: idx is always < 252
-                                target: unsafe { ConfigIndex::new_unchecked(idx as u8) },
-                                probability: prob,
-                            });
-                        }
-                    }
-                }
-
-                if !entries.is_empty() {
-                    transitions.insert(key, entries);
-                }
-            });
-        }
-
-        Self { transitions }
+        let rolls = std::array::from_fn(|to_roll| roll_outcomes(to_roll as u8));
+        Self { rolls }
     }
 
     /// Returns the transition distribution for a partial dice state.
     ///
-    /// Returns a slice of (target, probability) pairs for all reachable configurations.
-    pub fn get(&self, partial: &PartialDice) -> &[TransitionEntry] {
-        let key = PartialKey::from(partial);
-        self.transitions.get(&key).map(Vec::as_slice).unwrap_or(&[])
+    /// Yields (target, probability) pairs for all reachable configurations,
+    /// in canonical configuration order.
+    pub fn get(&self, partial: &PartialDice) -> Transitions<'_> {
+        Transitions::new(partial.kept_counts(), self.outcomes(partial.dice_to_roll()))
+    }
+
+    /// Returns the transition distribution for raw kept counts and dice to roll.
+    ///
+    /// Equivalent to [`get`](Self::get) without building a [`PartialDice`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DiceeError::InvalidDiceCount`] if the kept dice plus `to_roll`
+    /// don't total 5 dice.
+    pub fn get_by_counts(&self, kept: &[u8; 6], to_roll: u8) -> Result<Transitions<'_>> {
+        let total = kept.iter().map(|&c| usize::from(c)).sum::<usize>() + usize::from(to_roll);
+        if total != 5 {
+            return Err(DiceeError::InvalidDiceCount(total));
+        }
+
+        Ok(Transitions::new(kept, self.outcomes(to_roll)))
     }
 
     /// Computes expected value of a function over reachable configurations.
     ///
     /// E[f(config)] = Σ P(config | partial) × f(config)
-    pub fn expected_value<F>(&self, partial: &PartialDice, mut scorer: F) -> f64
+    pub fn expected_value<F>(&self, partial: &PartialDice, scorer: F) -> f64
     where
         F: FnMut(&DiceConfig) -> f64,
     {
-        let mut total = 0.0;
+        expected_value(self.get(partial), scorer)
+    }
 
-        for entry in self.get(partial) {
-            let config = DiceConfig::from_index(entry.target);
-            let value = scorer(&config);
-            total += entry.probability.get() * value;
-        }
+    /// Computes the exact expected value of a rational function over reachable configurations.
+    ///
+    /// The stored probabilities are floats, so each outcome's probability is
+    /// recomputed exactly from its rolled counts.
+    #[cfg(feature = "exact-rational")]
+    pub fn expected_value_exact<F>(
+        &self,
+        partial: &PartialDice,
+        scorer: F,
+    ) -> num_rational::Ratio<i64>
+    where
+        F: Fn(&DiceConfig) -> num_rational::Ratio<i64>,
+    {
+        use super::probability::exact::exact_roll_probability;
+
+        let to_roll = partial.dice_to_roll();
+        self.outcomes(to_roll)
+            .iter()
+            .map(|outcome| {
+                let target = combine(partial.kept_counts(), &outcome.rolled);
+                exact_roll_probability(&outcome.rolled, to_roll) * scorer(&target)
+            })
+            .sum()
+    }
 
-        total
+    /// Computes the probability that the resulting configuration satisfies `predicate`.
+    pub fn conditional_probability<F>(&self, partial: &PartialDice, mut predicate: F) -> Probability
+    where
+        F: FnMut(&DiceConfig) -> bool,
+    {
+        self.get(partial)
+            .configs()
+            .filter(|(config, _)| predicate(config))
+            .map(|(_, probability)| probability)
+            .sum()
     }
 
-    /// Returns the number of entries in the table.
+    /// Returns the most likely resulting configuration and its probability.
+    ///
+    /// Ties resolve to the first configuration in canonical order. Keeping all
+    /// dice returns the current configuration with probability 1.
+    pub fn mode_outcome(&self, partial: &PartialDice) -> (DiceConfig, Probability) {
+        let mut best: Option<(DiceConfig, Probability)> = None;
+        for (config, probability) in self.get(partial).configs() {
+            if best.is_none_or(|(_, p)| probability > p) {
+                best = Some((config, probability));
+            }
+        }
+        best.expect("every partial state has at least one outcome")
+    }
+
+    /// Returns the number of roll outcomes stored.
     pub fn entry_count(&self) -> usize {
-        self.transitions.values().map(Vec::len).sum()
+        self.rolls.iter().map(Vec::len).sum()
     }
 
-    /// Returns the number of distinct partial states tracked.
+    /// Returns the number of distinct partial states served.
     pub fn state_count(&self) -> usize {
-        self.transitions.len()
+        (0..=5).map(|to_roll| outcome_count(5 - to_roll)).sum()
     }
+
+    /// Returns the roll outcomes for rolling `to_roll` dice.
+    fn outcomes(&self, to_roll: u8) -> &[RollOutcome] {
+        self.rolls
+            .get(usize::from(to_roll))
+            .map_or(&[], Vec::as_slice)
+    }
+}
+
+// =============================================================================
+// LAZY TRANSITION TABLE
+// =============================================================================
+
+/// A transition table that computes each roll distribution on first use.
+///
+/// Results match [`TransitionTable`], but only the distributions for the
+/// dice-to-roll counts actually queried are resident, which keeps memory down
+/// on constrained targets such as WASM when few keeps are explored.
+#[derive(Debug, Default)]
+pub struct LazyTransitionTable {
+    /// Roll outcomes indexed by the number of dice rolled, computed on demand.
+    rolls: [OnceLock<Vec<RollOutcome>>; 6],
 }
 
-/// Computes the probability of transitioning from kept state to target.
-fn compute_transition_prob(
-    kept: &[u8; 6],
-    target: &DiceConfig,
-    to_roll: u8,
-) -> Option<Probability> {
-    // Compute what we need to roll to reach target
-    let target_counts = target.counts();
-    let mut needed = [0u8; 6];
-
-    for i in 0..6 {
-        if target_counts[i] < kept[i] {
-            // Target has fewer of this face than we're keeping: impossible
-            return None;
+impl LazyTransitionTable {
+    /// Creates an empty table; nothing is computed until first access.
+    pub const fn new() -> Self {
+        Self {
+            rolls: [const { OnceLock::new() }; 6],
         }
-        needed[i] = target_counts[i] - kept[i];
     }
 
-    // Verify the needed dice sum to what we're rolling
-    let needed_sum: u8 = needed.iter().sum();
-    if needed_sum != to_roll {
-        return None;
+    /// Returns the number of roll outcomes computed so far.
+    pub fn resident_count(&self) -> usize {
+        self.rolls
+            .iter()
+            .filter_map(OnceLock::get)
+            .map(Vec::len)
+            .sum()
     }
 
-    Some(roll_outcome_probability(&needed, to_roll))
+    /// Returns the roll outcomes for rolling `to_roll` dice, computing them if needed.
+    fn outcomes(&self, to_roll: u8) -> &[RollOutcome] {
+        self.rolls
+            .get(usize::from(to_roll))
+            .map_or(&[], |cell| cell.get_or_init(|| roll_outcomes(to_roll)))
+    }
+}
+
+// =============================================================================
+// TRANSITION SOURCE
+// =============================================================================
+
+/// Common interface of [`TransitionTable`] and [`LazyTransitionTable`], so
+/// code generic over it can use either.
+pub trait TransitionSource {
+    /// Returns the transition distribution for a partial dice state.
+    fn get(&self, partial: &PartialDice) -> Transitions<'_>;
+
+    /// Computes expected value of a function over reachable configurations.
+    fn expected_value<F>(&self, partial: &PartialDice, scorer: F) -> f64
+    where
+        F: FnMut(&DiceConfig) -> f64,
+    {
+        expected_value(self.get(partial), scorer)
+    }
+}
+
+impl TransitionSource for TransitionTable {
+    fn get(&self, partial: &PartialDice) -> Transitions<'_> {
+        TransitionTable::get(self, partial)
+    }
+}
+
+impl TransitionSource for LazyTransitionTable {
+    fn get(&self, partial: &PartialDice) -> Transitions<'_> {
+        Transitions::new(partial.kept_counts(), self.outcomes(partial.dice_to_roll()))
+    }
+}
+
+// =============================================================================
+// KEEP EVALUATION
+// =============================================================================
+
+/// Returns every valid keep pattern of `config` with its one-roll expected value under `scorer`.
+///
+/// Each keep is valued as the expectation of `scorer` over the
+/// configurations reachable by rerolling the other dice once. Keeping all
+/// dice values the configuration itself. Patterns are in
+/// [`KeepPattern::iter_valid_for`] order.
+///
+/// # Examples
+///
+/// ```rust
+/// use dicee_engine::core::DiceConfig;
+/// use dicee_engine::transition::keep_evs;
+///
+/// let config = DiceConfig::from_dice(&[1, 1, 2, 3, 4]);
+/// let evs = keep_evs(&config, |next| f64::from(next.sum()));
+///
+/// // Rerolling everything averages 17.5
+/// let (_, ev) = evs.iter().find(|(keep, _)| keep.total_kept() == 0).unwrap();
+/// assert!((ev - 17.5).abs() < 1e-9);
+/// ```
+pub fn keep_evs(
+    config: &DiceConfig,
+    scorer: impl Fn(&DiceConfig) -> f64,
+) -> Vec<(KeepPattern, f64)> {
+    KeepPattern::iter_valid_for(config)
+        .map(|keep| {
+            let partial = unsafe { PartialDice::new_unchecked(keep) };
+            (keep, TRANSITION_TABLE.expected_value(&partial, &scorer))
+        })
+        .collect()
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+/// Enumerates every outcome of rolling `to_roll` dice, in lexicographic order of rolled counts.
+fn roll_outcomes(to_roll: u8) -> Vec<RollOutcome> {
+    let mut outcomes = Vec::with_capacity(outcome_count(to_roll));
+    for_each_roll_outcome(to_roll, |rolled| {
+        outcomes.push(RollOutcome {
+            rolled: *rolled,
+            probability: roll_outcome_probability(rolled, to_roll),
+        });
+    });
+    outcomes
 }
 
-/// Enumerates all keep patterns that use exactly `total_kept` dice.
-fn for_each_keep_pattern<F>(total_kept: u8, mut f: F)
+/// Computes E[scorer(config)] over a transition distribution.
+fn expected_value<F>(transitions: Transitions<'_>, mut scorer: F) -> f64
 where
-    F: FnMut([u8; 6]),
+    F: FnMut(&DiceConfig) -> f64,
 {
-    fn recurse<F: FnMut([u8; 6])>(
-        kept: &mut [u8; 6],
-        face: usize,
-        remaining: u8,
-        max_per_face: u8,
-        f: &mut F,
-    ) {
-        if face == 5 {
-            if remaining <= max_per_face {
-                kept[5] = remaining;
-                f(*kept);
-            }
-            return;
-        }
+    let mut total = 0.0;
 
-        for c in 0..=remaining.min(max_per_face) {
-            kept[face] = c;
-            recurse(kept, face + 1, remaining - c, max_per_face, f);
-        }
+    for outcome in transitions.outcomes {
+        let config = combine(&transitions.kept, &outcome.rolled);
+        let value = scorer(&config);
+        total += outcome.probability.get() * value;
     }
 
-    let mut kept = [0u8; 6];
-    // Max dice per face when keeping
-    let max_per_face = 5u8;
-    recurse(&mut kept, 0, total_kept, max_per_face, &mut f);
+    total
+}
+
+/// Combines kept dice with rolled dice into the resulting configuration.
+#[inline]
+fn combine(kept: &[u8; 6], rolled: &[u8; 6]) -> DiceConfig {
+    let counts = [
+        kept[0] + rolled[0],
+        kept[1] + rolled[1],
+        kept[2] + rolled[2],
+        kept[3] + rolled[3],
+        kept[4] + rolled[4],
+        kept[5] + rolled[5],
+    ];
+    // Safety: kept + rolled always totals 5 dice
+    unsafe { DiceConfig::from_counts_unchecked(counts) }
 }
 
 // =============================================================================
@@ -224,7 +399,6 @@ pub static TRANSITION_TABLE: LazyLock<TransitionTable> = LazyLock::new(Transitio
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::keep::KeepPattern;
 
     #[test]
     fn test_table_builds() {
@@ -242,7 +416,7 @@ mod tests {
         let keep = KeepPattern::keep_all(&config);
         let partial = PartialDice::new(config, keep).unwrap();
 
-        let entries = table.get(&partial);
+        let entries: Vec<_> = table.get(&partial).collect();
         assert_eq!(entries.len(), 1);
         assert!((entries[0].probability.get() - 1.0).abs() < 1e-10);
     }
@@ -254,7 +428,7 @@ mod tests {
         // Keeping nothing: all 252 configs are reachable
         let partial = PartialDice::keep_none();
 
-        let entries = table.get(&partial);
+        let entries: Vec<_> = table.get(&partial).collect();
         assert_eq!(entries.len(), 252);
 
         // Probabilities should sum to 1
@@ -278,4 +452,201 @@ mod tests {
         // E[total] = 6 + 3 × 3.5 = 16.5
         assert!((ev - 16.5).abs() < 0.01);
     }
+
+    #[cfg(feature = "exact-rational")]
+    #[test]
+    fn test_expected_value_exact_matches_float() {
+        use num_rational::Ratio;
+
+        let table = &*TRANSITION_TABLE;
+        let config = DiceConfig::from_dice(&[3, 3, 4, 5, 6]);
+        let keep = KeepPattern::from_counts([0, 0, 2, 0, 0, 0]).unwrap();
+        let partial = PartialDice::new(config, keep).unwrap();
+
+        let exact =
+            table.expected_value_exact(&partial, |c| Ratio::from_integer(i64::from(c.sum())));
+        assert_eq!(exact, Ratio::new(33, 2));
+
+        let float = table.expected_value(&partial, |c| c.sum() as f64);
+        let converted = *exact.numer() as f64 / *exact.denom() as f64;
+        assert!((converted - float).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_table_size() {
+        let table = TransitionTable::build();
+        // C(k+5, 5) outcomes of rolling k dice, for k = 0..=5
+        assert_eq!(table.entry_count(), 1 + 6 + 21 + 56 + 126 + 252);
+        assert_eq!(table.state_count(), 252 + 126 + 56 + 21 + 6 + 1);
+
+        // Expanding every state's distribution would take over 9x the entries
+        let mut expanded = 0;
+        for to_roll in 0u8..=5 {
+            for_each_roll_outcome(5 - to_roll, |kept| {
+                expanded += table.get_by_counts(kept, to_roll).unwrap().len();
+            });
+        }
+        assert_eq!(expanded, 252 + 126 * 6 + 56 * 21 + 21 * 56 + 6 * 126 + 252);
+        assert!(expanded > 9 * table.entry_count());
+    }
+
+    #[test]
+    fn test_matches_direct_transitions() {
+        use crate::core::config::ALL_CONFIGS;
+        use crate::transition::probability::transition_probability;
+
+        let table = &*TRANSITION_TABLE;
+
+        for config in ALL_CONFIGS.iter() {
+            for keep in KeepPattern::iter_valid_for(config) {
+                let partial = PartialDice::new(*config, keep).unwrap();
+                let to_roll = partial.dice_to_roll();
+
+                // Distribution computed target-by-target, in canonical order
+                let expected: Vec<(ConfigIndex, f64)> = ALL_CONFIGS
+                    .iter()
+                    .filter_map(|target| {
+                        transition_probability(keep.counts(), target.counts(), to_roll)
+                            .map(|p| (target.to_index(), p.get()))
+                    })
+                    .collect();
+
+                let actual: Vec<(ConfigIndex, f64)> = table
+                    .get(&partial)
+                    .map(|e| (e.target, e.probability.get()))
+                    .collect();
+                assert_eq!(
+                    actual, expected,
+                    "Mismatch for {:?} with {:?}",
+                    config, keep
+                );
+
+                let direct_ev: f64 = expected
+                    .iter()
+                    .map(|&(target, p)| p * f64::from(DiceConfig::from_index(target).sum()))
+                    .sum();
+                let ev = table.expected_value(&partial, |c| f64::from(c.sum()));
+                assert_eq!(ev, direct_ev);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mode_outcome() {
+        let table = TransitionTable::build();
+
+        // From a full reroll, five distinct faces (multiplicity 120) are the most likely
+        let (config, probability) = table.mode_outcome(&PartialDice::keep_none());
+        assert_eq!(config.multiplicity(), 120);
+        assert_eq!(config, DiceConfig::from_dice(&[2, 3, 4, 5, 6]));
+        assert!((probability.get() - 120.0 / 7776.0).abs() < 1e-12);
+
+        // Keeping everything stays put with certainty
+        let current = DiceConfig::from_dice(&[2, 2, 4, 4, 6]);
+        let (config, probability) = table.mode_outcome(&PartialDice::keep_all(&current));
+        assert_eq!(config, current);
+        assert!(probability.is_one());
+
+        // Keeping four sixes, each final face is equally likely; the first in
+        // canonical (lexicographic counts) order wins
+        let keep = KeepPattern::from_counts([0, 0, 0, 0, 0, 4]).unwrap();
+        let partial = unsafe { PartialDice::new_unchecked(keep) };
+        let (config, probability) = table.mode_outcome(&partial);
+        assert_eq!(config, DiceConfig::from_dice(&[6, 6, 6, 6, 6]));
+        assert!((probability.get() - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_conditional_probability() {
+        let table = TransitionTable::build();
+
+        // Rolling all five dice: a Dicee is 6 / 7776
+        let p = table.conditional_probability(&PartialDice::keep_none(), |c| c.is_dicee());
+        assert!((p.get() - 6.0 / 7776.0).abs() < 1e-12);
+
+        // A certain and an impossible event
+        let p = table.conditional_probability(&PartialDice::keep_none(), |_| true);
+        assert!((p.get() - 1.0).abs() < 1e-9);
+        let p = table.conditional_probability(&PartialDice::keep_none(), |_| false);
+        assert!(p.is_zero());
+    }
+
+    #[test]
+    fn test_get_by_counts_matches_get() {
+        let table = TransitionTable::build();
+        let config = DiceConfig::from_dice(&[2, 2, 4, 5, 6]);
+
+        for keep in KeepPattern::iter_valid_for(&config) {
+            let partial = PartialDice::new(config, keep).unwrap();
+            let by_counts = table
+                .get_by_counts(keep.counts(), partial.dice_to_roll())
+                .unwrap();
+            let expected = table.get(&partial);
+
+            assert_eq!(by_counts.len(), expected.len());
+            for (a, b) in by_counts.zip(expected) {
+                assert_eq!(a.target, b.target);
+                assert_eq!(a.probability, b.probability);
+            }
+        }
+
+        assert!(matches!(
+            table.get_by_counts(&[1, 1, 0, 0, 0, 0], 2),
+            Err(DiceeError::InvalidDiceCount(4))
+        ));
+    }
+
+    #[test]
+    fn test_keep_evs() {
+        let config = DiceConfig::from_dice(&[6, 6, 6, 6, 5]);
+        let evs = keep_evs(&config, |next| f64::from(next.sum()));
+        assert_eq!(evs.len(), KeepPattern::iter_valid_for(&config).count());
+
+        // A high sum can only fall in expectation, so keeping everything is best
+        let (best_keep, best_ev) = evs
+            .iter()
+            .copied()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        assert_eq!(best_keep, KeepPattern::keep_all(&config));
+        assert!((best_ev - 29.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_lazy_matches_eager() {
+        let eager = TransitionTable::build();
+        let lazy = LazyTransitionTable::new();
+        assert_eq!(lazy.resident_count(), 0);
+
+        let config = DiceConfig::from_dice(&[1, 3, 3, 5, 6]);
+        let keeps = [
+            KeepPattern::KEEP_NONE,
+            KeepPattern::from_counts([0, 0, 2, 0, 0, 0]).unwrap(),
+            KeepPattern::from_counts([1, 0, 1, 0, 1, 1]).unwrap(),
+            KeepPattern::keep_all(&config),
+        ];
+
+        for keep in keeps {
+            let partial = PartialDice::new(config, keep).unwrap();
+            let expected: Vec<_> = eager.get(&partial).collect();
+            let actual: Vec<_> = lazy.get(&partial).collect();
+            assert_eq!(actual.len(), expected.len());
+            for (a, e) in actual.iter().zip(&expected) {
+                assert_eq!(a.target, e.target);
+                assert_eq!(a.probability, e.probability);
+            }
+
+            let sum = |c: &DiceConfig| f64::from(c.sum());
+            assert_eq!(
+                lazy.expected_value(&partial, sum),
+                TransitionSource::expected_value(&eager, &partial, sum)
+            );
+        }
+
+        // Only the distributions for 5, 3, 1 and 0 dice were computed
+        assert_eq!(
+            lazy.resident_count(),
+            outcome_count(5) + outcome_count(3) + outcome_count(1) + outcome_count(0)
+        );
+    }
 }
@@ -143,15 +143,23 @@ pub struct CategoryInfo {
     pub id: u8,
     pub name: &'static str,
     pub is_upper: bool,
+    /// Highest score attainable in this category
+    pub max_score: u16,
+    /// Probability that one fresh roll of 5 dice satisfies this category
+    pub base_probability: f64,
 }
 
 impl Category {
     /// Get metadata for this category
     pub fn info(&self) -> CategoryInfo {
+        let core = crate::core::Category::from_index(*self as usize)
+            .expect("legacy categories share indices");
         CategoryInfo {
             id: *self as u8,
             name: self.name(),
             is_upper: self.is_upper(),
+            max_score: crate::scoring::max_score(*self),
+            base_probability: core.fresh_roll_validity_probability(),
         }
     }
 }
@@ -34,7 +34,7 @@ fn arbitrary_rolls() -> impl Strategy<Value = u8> {
 /// Strategy for generating a non-empty category set.
 fn arbitrary_category_set() -> impl Strategy<Value = CategorySet> {
     // Generate a bitmask with at least one bit set (1 to 8191 = 2^13 - 1)
-    (1u16..=8191).prop_map(|bits| CategorySet::from_bits(bits))
+    (1u16..=8191).prop_map(CategorySet::from_bits)
 }
 
 /// Strategy for generating valid config index.
@@ -145,9 +145,8 @@ proptest! {
         let mut kept_counts = [0u8; 6];
         let mut dice_kept = 0u8;
 
-        for i in 0..5 {
+        for (i, &face) in canonical_dice.iter().enumerate() {
             if keep_bits & (1 << i) != 0 {
-                let face = canonical_dice[i];
                 kept_counts[(face - 1) as usize] += 1;
                 dice_kept += 1;
             }
@@ -268,6 +267,24 @@ proptest! {
         }
     }
 
+    /// Property: Every solved state satisfies the Bellman equation.
+    /// V(D, r, C) = max(best immediate score, best keep EV over V(·, r-1, C))
+    #[test]
+    fn prop_solver_satisfies_bellman(
+        dice in arbitrary_dice(),
+        rolls in arbitrary_rolls(),
+        categories in arbitrary_category_set()
+    ) {
+        let solver = TurnSolver::new();
+        let config = DiceConfig::from_dice(&dice);
+
+        prop_assert!(
+            solver.verify_bellman(&config, rolls, &categories),
+            "Bellman equation fails for {:?} with {} rolls and {}",
+            dice, rolls, categories
+        );
+    }
+
     /// Property: Empty category set yields zero EV.
     #[test]
     fn prop_empty_categories_zero_ev(